@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use advent_of_code_2023::{
+    dashboard::run_dashboard,
+    inputs::{get_inputs_key, lock_inputs, unlock_inputs, INPUTS_KEY_VAR},
+    leaderboard::{compute_standings, format_duration, Leaderboard},
+    puzzles::{extract_examples, puzzle_to_markdown, render_markdown},
+    runner::{
+        collect_input_files, load_expected_answers, run_day, ExpectedAnswers, RunHistory, RunRecord, RunStatus,
+        DEFAULT_ANSWER_COUNT,
+    },
+    utils::{aoc_base_url, fetch_aoc_page, parse_day},
+};
+
+const USAGE: &str = "usage:
+  aoc run DAY [INPUT_FILE | INPUT_DIR | GLOB]
+  aoc inputs (lock | unlock) [INPUT_DIR]
+  aoc read DAY
+  aoc leaderboard [--file LEADERBOARD_JSON | --id LEADERBOARD_ID [--event YEAR]] [--json]
+  aoc dashboard";
+
+// Event of the puzzles, and of the leaderboard unless `--event` is given
+const AOC_EVENT: &str = "2023";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|command| command.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("inputs") => inputs(&args[1..]),
+        Some("read") => read(&args[1..]),
+        Some("leaderboard") => leaderboard(&args[1..]),
+        Some("dashboard") => run_dashboard().unwrap_or_else(|err| exit_with_error(err)),
+        _ => exit_with_usage(),
+    }
+}
+
+fn run(args: &[String]) {
+    let day = args.first().and_then(|day| parse_day(day)).unwrap_or_else(|| exit_with_usage());
+    let pattern = args.get(1).cloned().unwrap_or_else(|| format!("inputs/day-{}.txt", day));
+
+    let inputs = collect_input_files(&pattern).unwrap_or_else(|err| exit_with_error(err));
+
+    let mut expected_answers_by_dir: HashMap<PathBuf, Option<ExpectedAnswers>> = HashMap::new();
+    let mut run_history = RunHistory::load().unwrap_or_else(|err| exit_with_error(err));
+    let mut rows = vec![vec![
+        "Input".to_string(),
+        "Answers".to_string(),
+        "Time".to_string(),
+        "Check".to_string(),
+        "Stderr".to_string(),
+    ]];
+    let mut failures = 0;
+
+    for input in inputs.iter() {
+        let dir = input.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let expected_answers = expected_answers_by_dir
+            .entry(dir)
+            .or_insert_with_key(|dir| load_expected_answers(dir).unwrap_or_else(|err| exit_with_error(err)))
+            .as_ref()
+            .and_then(|expected_answers| expected_answers.get(input));
+
+        let day_run = run_day(&day, input).unwrap_or_else(|err| exit_with_error(err));
+        let answer_count = expected_answers.map_or(DEFAULT_ANSWER_COUNT, |expected| expected.len());
+        let record = RunRecord::from_day_run(&day_run, expected_answers, answer_count);
+
+        let check = match record.status {
+            RunStatus::Passed => "ok".to_string(),
+            RunStatus::Mismatch => format!("MISMATCH (expected {})", expected_answers.unwrap().join(" | ")),
+            RunStatus::Crashed => "FAILED".to_string(),
+            RunStatus::Unchecked => "-".to_string(),
+        };
+
+        if matches!(record.status, RunStatus::Mismatch | RunStatus::Crashed) {
+            failures += 1;
+        }
+
+        rows.push(vec![
+            input.display().to_string(),
+            record.answers.join(" | "),
+            format!("{:.2?}", record.elapsed),
+            check,
+            day_run.last_error().unwrap_or_default().to_string(),
+        ]);
+        run_history.record(&day, record);
+    }
+
+    run_history.save().unwrap_or_else(|err| exit_with_error(err));
+    print_table(&rows);
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn inputs(args: &[String]) {
+    let dir = Path::new(args.get(1).map_or("inputs", |dir| dir.as_str()));
+    let passphrase = get_inputs_key()
+        .unwrap_or_else(|| exit_with_error(format!("No passphrase set in ${}", INPUTS_KEY_VAR)));
+
+    let files = match args.first().map(|action| action.as_str()) {
+        Some("lock") => lock_inputs(dir, &passphrase),
+        Some("unlock") => unlock_inputs(dir, &passphrase),
+        _ => exit_with_usage(),
+    }
+    .unwrap_or_else(|err| exit_with_error(err));
+
+    for file in files.iter() {
+        println!("{}", file.display());
+    }
+    println!("{} file(s) written", files.len());
+}
+
+fn read(args: &[String]) {
+    let day = args.first().and_then(|day| parse_day(day)).unwrap_or_else(|| exit_with_usage());
+
+    let html = fetch_aoc_page(&format!("{}/day/{}", AOC_EVENT, day.trim_start_matches('0')))
+        .unwrap_or_else(|err| exit_with_error(err));
+    let markdown = puzzle_to_markdown(&html, &aoc_base_url());
+    if markdown.trim().is_empty() {
+        exit_with_error(format!("No puzzle text found for day {}", day));
+    }
+
+    let puzzle_path = PathBuf::from(format!("puzzles/day-{}.md", day));
+    fs::create_dir_all("puzzles")
+        .and_then(|_| fs::write(&puzzle_path, &markdown))
+        .unwrap_or_else(|err| exit_with_error(err));
+
+    let mut written_files = vec![puzzle_path];
+    for (i, example) in extract_examples(&html).iter().enumerate() {
+        let sample_path = match i {
+            0 => PathBuf::from(format!("sample-inputs/day-{}.txt", day)),
+            _ => PathBuf::from(format!("sample-inputs/day-{}-{}.txt", day, i + 1)),
+        };
+
+        // Keep hand-made sample inputs, but replace the placeholder from `new-day.sh`
+        let existing = fs::read_to_string(&sample_path).unwrap_or_default();
+        if !existing.is_empty() && !existing.starts_with("Sample input for day") {
+            continue;
+        }
+
+        fs::write(&sample_path, example).unwrap_or_else(|err| exit_with_error(err));
+        written_files.push(sample_path);
+    }
+
+    print!("{}", render_markdown(&markdown));
+    println!();
+    for file in written_files.iter() {
+        println!("Wrote {}", file.display());
+    }
+}
+
+fn leaderboard(args: &[String]) {
+    let mut file = None;
+    let mut id = env::var("AOC_LEADERBOARD_ID").ok();
+    let mut as_json = false;
+    let mut event = AOC_EVENT.to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => file = Some(args.next().unwrap_or_else(|| exit_with_usage()).clone()),
+            "--id" => id = Some(args.next().unwrap_or_else(|| exit_with_usage()).clone()),
+            "--event" => event = args.next().unwrap_or_else(|| exit_with_usage()).clone(),
+            "--json" => as_json = true,
+            _ => exit_with_usage(),
+        }
+    }
+
+    let json = match (file, id) {
+        (Some(file), _) => fs::read_to_string(file),
+        (None, Some(id)) => fetch_aoc_page(&format!("{}/leaderboard/private/view/{}.json", event, id)),
+        (None, None) => exit_with_error("No leaderboard given, use --file, --id or set $AOC_LEADERBOARD_ID"),
+    }
+    .unwrap_or_else(|err| exit_with_error(err));
+    let leaderboard: Leaderboard = json.parse().unwrap_or_else(|err| exit_with_error(err));
+    let standings = compute_standings(&leaderboard).unwrap_or_else(|err| exit_with_error(err));
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&standings).expect("Error serializing standings"));
+        return;
+    }
+
+    let mut rows = vec![vec![
+        "Rank".to_string(),
+        "Member".to_string(),
+        "Score".to_string(),
+        "Reported".to_string(),
+        "Stars".to_string(),
+    ]];
+    for (rank, standing) in standings.iter().enumerate() {
+        rows.push(vec![
+            (rank + 1).to_string(),
+            standing.name.clone(),
+            standing.local_score.to_string(),
+            standing.reported_local_score.to_string(),
+            standing.stars.to_string(),
+        ]);
+    }
+    print_table(&rows);
+
+    let format_time = |time: Option<i64>| time.map_or("-".to_string(), format_duration);
+    for day in 1..=25 {
+        let finishers = standings
+            .iter()
+            .filter_map(|standing| standing.days.get(&day).map(|completion| (standing, completion)))
+            .collect::<Vec<_>>();
+        if finishers.is_empty() {
+            continue;
+        }
+
+        println!("\nDay {}", day);
+        let mut rows = vec![vec![
+            "Member".to_string(),
+            "Part 1".to_string(),
+            "Part 2".to_string(),
+            "Delta".to_string(),
+        ]];
+        for (standing, completion) in finishers {
+            rows.push(vec![
+                standing.name.clone(),
+                format_time(completion.part_1_time),
+                format_time(completion.part_2_time),
+                format_time(completion.part_2_delta),
+            ]);
+        }
+        print_table(&rows);
+    }
+}
+
+fn print_table(rows: &[Vec<String>]) {
+    let widths = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    for row in rows {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn exit_with_usage() -> ! {
+    println!("{}", USAGE);
+    process::exit(1);
+}
+
+fn exit_with_error(err: impl std::fmt::Display) -> ! {
+    eprintln!("Error: {}", err);
+    process::exit(1);
+}
@@ -1,12 +1,16 @@
-use std::{str::Chars, collections::HashSet};
-use lazy_static::lazy_static;
-use advent_of_code_2023::utils::get_aoc_input_lines;
-use either::Either;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    fs,
+};
+
+use advent_of_code_2023::utils::{get_all_flag_values, get_aoc_input_lines, has_flag};
+use itertools::Itertools;
 use phf::phf_map;
 
-const SPELLED_DIGITS_MIN_LENGTH: usize = 3;
-const SPELLED_DIGITS_MAX_LENGTH: usize = 5;
-static SPELLED_DIGITS_MAP: phf::Map<&'static str, usize> = phf_map! {
+// Vocabulary used when no `--vocabulary FILE` is given. Vocabulary files are TOML tables
+// mapping each word to its digit, e.g. `un = 1`.
+static SPELLED_DIGITS_MAP: phf::Map<&'static str, u32> = phf_map! {
     "one" => 1,
     "two" => 2,
     "three" => 3,
@@ -18,102 +22,257 @@ static SPELLED_DIGITS_MAP: phf::Map<&'static str, usize> = phf_map! {
     "nine" => 9,
 };
 
-lazy_static!  {
-    #[derive(Debug)]
-    static ref SPELLED_DIGITS_PREFIXES_SET: HashSet<&'static str> = SPELLED_DIGITS_MAP.keys().flat_map(|key| {
-        let mut keys = Vec::with_capacity(key.len());
+fn main() {
+    let vocabulary = get_vocabulary_from_args();
+    let explain = has_flag("--explain");
+    let only_disagreements = has_flag("--disagreements");
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let lines = input_lines.map(|line| line.expect("Error reading line")).collect_vec();
+
+    let digits_matcher = TokenMatcher::new(&HashMap::new());
+    let words_matcher = TokenMatcher::new(&vocabulary);
 
-        // Add all prefixes of the key (including the key itself)
-        for i in 1..key.len() + 1 {
-            keys.push(key.get(0..i).unwrap());
-        }
-        
-        keys
-    }).collect();
+    if explain || only_disagreements {
+        explain_calibrations(&digits_matcher, &words_matcher, &lines, only_disagreements);
+    }
+
+    println!("Calibration sum - Part 1: {}", sum_calibration_values(&digits_matcher, &lines));
+    println!("Calibration sum with spelled digits - Part 2: {}", sum_calibration_values(&words_matcher, &lines));
+}
+
+/// Merges every `--vocabulary FILE` given, or falls back to the English digits.
+fn get_vocabulary_from_args() -> HashMap<String, u32> {
+    let files = get_all_flag_values("--vocabulary", 1).into_iter().map(|values| values[0].clone()).collect_vec();
+
+    if files.is_empty() {
+        return SPELLED_DIGITS_MAP.entries().map(|(word, &digit)| (word.to_string(), digit)).collect();
+    }
 
-    #[derive(Debug)]
-    static ref SPELLED_DIGITS_SUFFIXES_SET: HashSet<&'static str> = SPELLED_DIGITS_MAP.keys().flat_map(|key| {
-        let mut keys = Vec::with_capacity(key.len());
+    files.iter().flat_map(|file| load_vocabulary(file)).collect()
+}
+
+fn load_vocabulary(file: &str) -> HashMap<String, u32> {
+    let table = fs::read_to_string(file)
+        .expect("Error reading vocabulary file")
+        .parse::<toml::Table>()
+        .expect("Error parsing vocabulary file");
+
+    table
+        .into_iter()
+        .map(|(word, digit)| {
+            let digit = digit
+                .as_integer()
+                .and_then(|digit| u32::try_from(digit).ok())
+                .filter(|&digit| digit <= 9)
+                .unwrap_or_else(|| panic!("Error parsing vocabulary, {} should map to a digit", word));
+            (word, digit)
+        })
+        .collect()
+}
 
-        // Add all suffixes of the key
-        for i in 0..key.len() + 1 {
-            keys.push(key.get(i..key.len()).unwrap());
+fn sum_calibration_values(matcher: &TokenMatcher, lines: &[String]) -> u32 {
+    lines
+        .iter()
+        .filter_map(|line| matcher.find_calibration(line))
+        .map(|calibration| calibration.value())
+        .sum()
+}
+
+/// Prints both readings of each line, or only of the lines where they give different values.
+fn explain_calibrations(digits_matcher: &TokenMatcher, words_matcher: &TokenMatcher, lines: &[String], only_disagreements: bool) {
+    for (i, line) in lines.iter().enumerate() {
+        let digits_calibration = digits_matcher.find_calibration(line);
+        let words_calibration = words_matcher.find_calibration(line);
+        let values = (
+            digits_calibration.as_ref().map(Calibration::value),
+            words_calibration.as_ref().map(Calibration::value),
+        );
+        if only_disagreements && values.0 == values.1 {
+            continue;
         }
-        
-        keys
-    }).collect();
+
+        println!("{}: {}", i + 1, line);
+        println!("  digits:  {}", Calibration::explain(digits_calibration.as_ref(), line));
+        println!("  spelled: {}", Calibration::explain(words_calibration.as_ref(), line));
+    }
 }
 
-fn main() {
-    let input_lines = get_aoc_input_lines().expect("Error getting input");
+/// A digit or a word matched in a line, `start..end` being its byte range.
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    start: usize,
+    end: usize,
+    digit: u32,
+}
 
-    let mut sum = 0;
+#[derive(Debug)]
+struct Calibration {
+    first: Token,
+    last: Token,
+}
 
-    for line in input_lines {
-        let line = line.expect("Error reading line");
-        let mut chars = line.chars();
-        
-        let first_digit = match get_digit(&mut chars, false) {
-            Some(digit) => digit,
-            None => continue,
-        };
-        
-        let second_digit = match get_digit(&mut chars, true) {
-            Some(digit) => digit,
-            None => first_digit,
+impl Calibration {
+    fn value(&self) -> u32 {
+        10 * self.first.digit + self.last.digit
+    }
+
+    fn explain(calibration: Option<&Self>, line: &str) -> String {
+        let Some(calibration) = calibration else {
+            return "no digit found".to_string();
         };
 
-        sum += 10 * first_digit + second_digit;
+        let describe = |token: &Token| format!("{:?} at {}..{}", &line[token.start..token.end], token.start, token.end);
+        format!(
+            "first {}, last {} -> {}",
+            describe(&calibration.first),
+            describe(&calibration.last),
+            calibration.value()
+        )
     }
+}
 
-    println!("{}", sum);
+/// Aho-Corasick automaton over the bytes of the digits and the vocabulary words, reporting
+/// every match in a single pass, overlapping ones included (e.g. both words of "oneight").
+#[derive(Debug)]
+struct TokenMatcher {
+    transitions: Vec<HashMap<u8, usize>>,
+    failures: Vec<usize>,
+    /// `(length, digit)` of the tokens ending at each state, including through failure links.
+    outputs: Vec<Vec<(usize, u32)>>,
 }
 
-fn get_digit(chars: &mut Chars<'_>, rev: bool) -> Option<u32> {
-    let mut spelled_digit = String::with_capacity(SPELLED_DIGITS_MAX_LENGTH);
-    
-    let chars = if rev {
-        Either::Left(chars.rev())
-    } else {
-        Either::Right(chars)
-    };
-
-    for char in chars {
-        // Try to parse the character as a digit.
-        if char.is_digit(10) {
-            return char.to_digit(10);
+impl TokenMatcher {
+    const ROOT: usize = 0;
+
+    fn new(vocabulary: &HashMap<String, u32>) -> Self {
+        let mut matcher = Self {
+            transitions: vec![HashMap::new()],
+            failures: vec![Self::ROOT],
+            outputs: vec![vec![]],
+        };
+
+        let digits = (0..=9).map(|digit| (digit.to_string(), digit));
+        for (word, digit) in digits.chain(vocabulary.iter().map(|(word, &digit)| (word.clone(), digit))) {
+            matcher.insert(&word, digit);
         }
+        matcher.link_failures();
+
+        matcher
+    }
 
-        // The character is not a digit, so add it to the spelled digit.
-        if rev {
-            spelled_digit.insert(0, char);
-        } else {
-            spelled_digit.push(char);
+    fn insert(&mut self, word: &str, digit: u32) {
+        let mut state = Self::ROOT;
+        for byte in word.bytes() {
+            state = match self.transitions[state].get(&byte) {
+                Some(&next) => next,
+                None => {
+                    self.transitions.push(HashMap::new());
+                    self.failures.push(Self::ROOT);
+                    self.outputs.push(vec![]);
+                    let next = self.transitions.len() - 1;
+                    self.transitions[state].insert(byte, next);
+                    next
+                }
+            };
         }
 
-        // If spelled_digit is a not prefix of a spelled digit, continue.
-        if rev {
-            while !SPELLED_DIGITS_SUFFIXES_SET.contains(spelled_digit.as_str()) && spelled_digit.len() != 0 {
-                spelled_digit.pop();
-            }
-        } else {
-            while !SPELLED_DIGITS_PREFIXES_SET.contains(spelled_digit.as_str()) && spelled_digit.len() != 0 {
-                spelled_digit.remove(0);
+        self.outputs[state].push((word.len(), digit));
+    }
+
+    /// Links each state to the state of its longest proper suffix, breadth first so that
+    /// shorter suffixes are linked before they are needed.
+    fn link_failures(&mut self) {
+        let mut queue: VecDeque<usize> = self.transitions[Self::ROOT].values().copied().collect();
+
+        while let Some(state) = queue.pop_front() {
+            for (byte, next) in self.transitions[state].clone() {
+                let failure = self.step(self.failures[state], byte);
+                self.failures[next] = failure;
+                let inherited = self.outputs[failure].clone();
+                self.outputs[next].extend(inherited);
+                queue.push_back(next);
             }
         }
+    }
 
-        // If the spelled digit is too long, return None.
-        if spelled_digit.len() > SPELLED_DIGITS_MAX_LENGTH || spelled_digit.len() == 0 {
-            continue;
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.transitions[state].get(&byte) {
+                return next;
+            }
+            if state == Self::ROOT {
+                return Self::ROOT;
+            }
+            state = self.failures[state];
         }
+    }
 
-        // If the spelled digit is long enough, try to parse it.
-        if spelled_digit.len() >= SPELLED_DIGITS_MIN_LENGTH {
-            if let Some(digit) = SPELLED_DIGITS_MAP.get(spelled_digit.as_str()) {
-                return Some(*digit as u32);
+    fn find_tokens(&self, line: &str) -> Vec<Token> {
+        let mut state = Self::ROOT;
+        let mut tokens = vec![];
+
+        for (i, byte) in line.bytes().enumerate() {
+            state = self.step(state, byte);
+            for &(length, digit) in self.outputs[state].iter() {
+                tokens.push(Token {
+                    start: i + 1 - length,
+                    end: i + 1,
+                    digit,
+                });
             }
         }
+
+        tokens
     }
 
-    None
-}
\ No newline at end of file
+    /// The first and last tokens of the line, preferring the longest one when several start together.
+    fn find_calibration(&self, line: &str) -> Option<Calibration> {
+        let tokens = self.find_tokens(line);
+        let first = *tokens.iter().min_by_key(|token| (token.start, Reverse(token.end)))?;
+        let last = *tokens.iter().max_by_key(|token| (token.start, token.end))?;
+
+        Some(Calibration { first, last })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> HashMap<String, u32> {
+        SPELLED_DIGITS_MAP.entries().map(|(word, &digit)| (word.to_string(), digit)).collect()
+    }
+
+    fn calibration_value(matcher: &TokenMatcher, line: &str) -> Option<u32> {
+        matcher.find_calibration(line).map(|calibration| calibration.value())
+    }
+
+    #[test]
+    fn overlapping_words_are_both_matched() {
+        let matcher = TokenMatcher::new(&english());
+        assert_eq!(calibration_value(&matcher, "oneight"), Some(18));
+        assert_eq!(calibration_value(&matcher, "twone"), Some(21));
+        assert_eq!(calibration_value(&matcher, "xtwone3four"), Some(24));
+    }
+
+    #[test]
+    fn words_are_ignored_without_vocabulary() {
+        let matcher = TokenMatcher::new(&HashMap::new());
+        assert_eq!(calibration_value(&matcher, "one2threefour5six"), Some(25));
+    }
+
+    #[test]
+    fn line_without_digit_has_no_calibration() {
+        let matcher = TokenMatcher::new(&english());
+        assert_eq!(calibration_value(&matcher, "abcdef"), None);
+        assert_eq!(calibration_value(&matcher, ""), None);
+    }
+
+    #[test]
+    fn merged_vocabularies_match_words_of_each() {
+        let french = HashMap::from([("deux".to_string(), 2), ("neuf".to_string(), 9)]);
+        let matcher = TokenMatcher::new(&english().into_iter().chain(french).collect());
+        assert_eq!(calibration_value(&matcher, "deuxthreeneuf"), Some(29));
+        assert_eq!(calibration_value(&matcher, "sevendeux"), Some(72));
+    }
+}
@@ -1,4 +1,11 @@
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use std::{collections::BTreeMap, fs};
+
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values, has_flag};
+use itertools::Itertools;
+
+// Bag used when neither `--bag red=12,green=13,blue=14` nor `--bag-file FILE` is given.
+// Bag files are TOML tables mapping each color to its count, e.g. `red = 12`.
+const DEFAULT_BAG: &str = "red=12,green=13,blue=14";
 
 #[derive(Debug)]
 struct Game {
@@ -6,50 +13,72 @@ struct Game {
     reveals: Vec<Reveal>,
 }
 
-#[derive(Debug)]
+/// Number of cubes of each color, colors not listed counting as 0.
+#[derive(Debug, Clone, Default)]
 struct Reveal {
-    red: usize,
-    green: usize,
-    blue: usize,
+    counts: BTreeMap<String, usize>,
 }
 
-const VALID_REVEAL_CEILINGS: Reveal = Reveal {
-    red: 12,
-    green: 13,
-    blue: 14,
-};
-
 fn main() {
+    let bag = get_bag_from_args();
+    let show_failures = has_flag("--failures");
+
     let input_lines = get_aoc_input_lines().expect("Error getting input");
-    let mut sum_valid_game_ids = 0;
-    let mut sum_min_reveal_power = 0;
+    let games = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Game::from_input(&line))
+        .collect_vec();
+
+    if show_failures {
+        for game in games.iter() {
+            if let Some((i, exceeded)) = game.find_failing_reveal(&bag) {
+                let exceeded = exceeded
+                    .iter()
+                    .map(|(color, count)| format!("{} {} > {}", count, color, bag.count(color)))
+                    .join(", ");
+                println!("Game {} fails on reveal {}: {}", game.id, i + 1, exceeded);
+            }
+        }
+    }
 
-    for line in input_lines {
-        let line = line.expect("Error reading line");
+    let min_bag = games.iter().fold(Reveal::default(), |min_bag, game| min_bag.union(&game.max_reveal()));
+    println!("Minimum bag for all games: {} (power {})", min_bag, min_bag.power(&bag));
 
-        let game = Game::from_input(&line);
+    let sum_valid_game_ids: usize = games.iter().filter(|game| game.is_valid(&bag)).map(|game| game.id).sum();
+    let sum_min_reveal_power: usize = games.iter().map(|game| game.max_reveal_power(&bag)).sum();
 
-        if game.is_valid() {
-            sum_valid_game_ids += game.id;
-        }
+    println!("Part A: {}", sum_valid_game_ids);
+    println!("Part B: {}", sum_min_reveal_power);
+}
 
-        sum_min_reveal_power += game.max_reveal_power();
+fn get_bag_from_args() -> Reveal {
+    if let Some(values) = get_flag_values("--bag-file", 1) {
+        return Reveal::from_toml(&fs::read_to_string(&values[0]).expect("Error reading bag file"));
     }
 
-    println!("Part A: {}", sum_valid_game_ids);
-    println!("Part B: {}", sum_min_reveal_power);
+    let spec = get_flag_values("--bag", 1).map_or(DEFAULT_BAG.to_string(), |values| values[0].clone());
+    let counts = spec
+        .split(',')
+        .map(|entry| {
+            let (color, count) = entry.split('=').collect_tuple().expect("Error parsing bag, expected COLOR=COUNT");
+            (color.trim().to_string(), count.trim().parse::<usize>().expect("Error parsing bag count"))
+        })
+        .collect();
+
+    Reveal { counts }
 }
 
 impl Game {
-    fn from_input(line: &String) -> Game {
+    fn from_input(line: &str) -> Game {
         // Get the id
-        let id = line.split(":").next().expect("Error getting id");
-        let id = id.split(" ").last().expect("Error getting id");
+        let id = line.split(':').next().expect("Error getting id");
+        let id = id.split(' ').next_back().expect("Error getting id");
         let id = id.parse::<usize>().expect("Error parsing id");
 
         // Get the reveals
-        let reveals = line.split(":").last().expect("Error getting reveals");
-        let reveals = reveals.split(";").map(Reveal::from_input).into_iter();
+        let reveals = line.split(':').next_back().expect("Error getting reveals");
+        let reveals = reveals.split(';').map(Reveal::from_input);
 
         Game {
             id,
@@ -57,72 +86,100 @@ impl Game {
         }
     }
 
-    fn is_valid(&self) -> bool {
-        self.reveals.iter().all(|reveal| reveal.is_valid())
+    fn is_valid(&self, bag: &Reveal) -> bool {
+        self.find_failing_reveal(bag).is_none()
     }
 
-    fn max_reveal(&self) -> Reveal {
-        let mut max_reveal = Reveal {
-            red: 0,
-            green: 0,
-            blue: 0,
-        };
-
-        self.reveals.iter().for_each(|reveal| {
-            if reveal.red > max_reveal.red {
-                max_reveal.red = reveal.red;
-            }
-            if reveal.green > max_reveal.green {
-                max_reveal.green = reveal.green;
-            }
-            if reveal.blue > max_reveal.blue {
-                max_reveal.blue = reveal.blue;
-            }
-        });
+    /// The first reveal that does not fit in the bag, with the colors it has too many of.
+    fn find_failing_reveal(&self, bag: &Reveal) -> Option<(usize, Vec<(&str, usize)>)> {
+        self.reveals.iter().enumerate().find_map(|(i, reveal)| {
+            let exceeded = reveal.exceeded_colors(bag);
+            (!exceeded.is_empty()).then_some((i, exceeded))
+        })
+    }
 
-        max_reveal
+    /// The smallest bag this game could have been played with.
+    fn max_reveal(&self) -> Reveal {
+        self.reveals.iter().fold(Reveal::default(), |max_reveal, reveal| max_reveal.union(reveal))
     }
 
-    fn max_reveal_power(&self) -> usize {
-        self.max_reveal().power()
+    fn max_reveal_power(&self, bag: &Reveal) -> usize {
+        self.max_reveal().power(bag)
     }
 }
 
-
-
 impl Reveal {
     fn from_input(line: &str) -> Reveal {
-        let mut reveal = Reveal {
-            red: 0,
-            green: 0,
-            blue: 0,
-        };
-
-        line.split(",").for_each(|color_input| {
-            let count = color_input
-                .trim()
-                .split(" ")
-                .next()
-                .expect("Error getting color count");
-            let count = count.parse::<usize>().expect("Error parsing color count");
-            match color_input.split(" ").last().expect("Error getting color") {
-                "red" => reveal.red = count,
-                "green" => reveal.green = count,
-                "blue" => reveal.blue = count,
-                _ => panic!("Error parsing color"),
-            };
-        });
-
-        reveal
+        let counts = line
+            .split(',')
+            .filter(|color_input| !color_input.trim().is_empty())
+            .map(|color_input| {
+                let (count, color) = color_input
+                    .split_whitespace()
+                    .collect_tuple()
+                    .expect("Error parsing color count");
+                let count = count.parse::<usize>().expect("Error parsing color count");
+                (color.to_string(), count)
+            })
+            .collect();
+
+        Reveal { counts }
     }
 
-    fn is_valid(&self) -> bool {
-        self.red <= VALID_REVEAL_CEILINGS.red
-            && self.green <= VALID_REVEAL_CEILINGS.green
-            && self.blue <= VALID_REVEAL_CEILINGS.blue
+    fn from_toml(input: &str) -> Reveal {
+        let table = input.parse::<toml::Table>().expect("Error parsing bag file");
+        let counts = table
+            .into_iter()
+            .map(|(color, count)| {
+                let count = count
+                    .as_integer()
+                    .and_then(|count| usize::try_from(count).ok())
+                    .unwrap_or_else(|| panic!("Error parsing bag count of {}", color));
+                (color, count)
+            })
+            .collect();
+
+        Reveal { counts }
     }
 
-    fn power(&self) -> usize {
-        self.red * self.green * self.blue
+    fn count(&self, color: &str) -> usize {
+        self.counts.get(color).copied().unwrap_or(0)
     }
-}
\ No newline at end of file
+
+    fn exceeded_colors(&self, bag: &Reveal) -> Vec<(&str, usize)> {
+        self.counts
+            .iter()
+            .filter(|(color, &count)| count > bag.count(color))
+            .map(|(color, &count)| (color.as_str(), count))
+            .collect()
+    }
+
+    /// The largest count of each color in either reveal.
+    fn union(&self, other: &Reveal) -> Reveal {
+        let mut counts = self.counts.clone();
+        for (color, &count) in other.counts.iter() {
+            let max_count = counts.entry(color.clone()).or_insert(0);
+            *max_count = (*max_count).max(count);
+        }
+
+        Reveal { counts }
+    }
+
+    /// Product of the counts of every color seen in the reveal or held by the bag, so that
+    /// a color of the bag that never shows up makes the power 0.
+    fn power(&self, bag: &Reveal) -> usize {
+        self.counts
+            .keys()
+            .chain(bag.counts.keys())
+            .unique()
+            .map(|color| self.count(color))
+            .product()
+    }
+}
+
+impl std::fmt::Display for Reveal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts = self.counts.iter().map(|(color, count)| format!("{} {}", count, color)).join(", ");
+        write!(f, "{}", counts)
+    }
+}
@@ -1,217 +1,313 @@
 use std::{
-    cmp::max,
-    collections::{HashMap, HashSet},
-    iter::Peekable,
-    str::Chars,
+    collections::{BTreeMap, HashMap},
+    fs,
+    ops::Range,
 };
 
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values, has_flag};
+use itertools::Itertools;
+
+const GEAR: char = '*';
+const GEAR_NUMBERS: usize = 2;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_DIM: &str = "\x1b[2m";
+const HTML_STYLE: &str = "body { background: #10101a; color: #666; }
+.part { color: #4caf50; }
+.orphan { color: #f44336; }
+.gear { color: #ffeb3b; font-weight: bold; }
+.symbol { color: #00bcd4; }
+.ratio { color: #ffeb3b; }";
 
 fn main() {
     let input_lines = get_aoc_input_lines().expect("Error getting input");
-    let mut sum_part_numbers = 0;
-    let mut sum_gear_ratios = 0;
+    let schematic = Schematic::from_lines(input_lines.map(|line| line.expect("Error reading line")));
 
-    let mut gears = HashMap::<(i32, i32), Symbol>::new();
+    // `--touching N [--symbol SYMBOL]`: symbols touching exactly N numbers, optionally of a single kind
+    if let Some(values) = get_flag_values("--touching", 1) {
+        let count = values[0].parse::<usize>().expect("Error parsing --touching, expected a number count");
+        let kind = get_flag_values("--symbol", 1).map(|values| {
+            values[0].chars().exactly_one().expect("Error parsing --symbol, expected a single character")
+        });
+        let symbols = schematic.symbols_touching_exactly(kind, count).collect_vec();
+        let products: u64 = symbols.iter().map(|&symbol| schematic.neighbours_product(symbol)).sum();
+        println!("Symbols touching exactly {} numbers: {} (sum of products {})", count, symbols.len(), products);
+    }
 
-    let mut previous_line_symbol_idxs = HashSet::<i32>::new();
-    let mut previous_line_part_numbers = Vec::<PartNumber>::new();
+    if has_flag("--per-symbol") {
+        for (kind, sum) in schematic.sum_per_symbol_kind() {
+            println!("Numbers touching {}: {}", kind, sum);
+        }
+    }
 
-    let mut current_line_symbol_idxs = Vec::<i32>::new();
-    let mut current_line_part_numbers = Vec::<PartNumber>::new();
+    if has_flag("--render") {
+        print!("{}", schematic.render_ansi());
+    }
 
-    let mut line_idx = 0;
-    for line in input_lines {
-        let line = line.expect("Error reading line");
+    if let Some(values) = get_flag_values("--html", 1) {
+        fs::write(&values[0], schematic.render_html()).expect("Error writing HTML file");
+    }
 
-        let mut chars = line.chars().peekable();
-        let mut idx = 0;
+    println!("Part A: {}", schematic.sum_part_numbers());
+    println!("Part B: {}", schematic.sum_gear_ratios());
+}
 
-        loop {
-            if chars.peek().is_none() {
-                break;
-            }
+#[derive(Debug, Clone)]
+struct NumberSpan {
+    value: u64,
+    row: usize,
+    columns: Range<usize>,
+}
 
-            let symbol = next_symbol_idx(&mut chars, idx);
-            if symbol.is_some() {
-                let symbol = symbol.unwrap();
-                idx = symbol.idx + 1; // Symbol index is inclusive, so add 1
+#[derive(Debug, Clone)]
+struct Symbol {
+    kind: char,
+    row: usize,
+    column: usize,
+}
 
-                if symbol.symbol_type == SymbolType::Gear {
-                    gears.insert((-line_idx, symbol.idx), symbol.clone());
-                }
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Highlight {
+    PartNumber,
+    OrphanNumber,
+    Gear,
+    OtherSymbol,
+}
 
-                if symbol.symbol_type == SymbolType::Other || symbol.symbol_type == SymbolType::Gear
-                {
-                    current_line_symbol_idxs.push(symbol.idx);
-
-                    let mut validated_idxs = HashSet::<i32>::new();
-                    for part_number in previous_line_part_numbers.iter() {
-                        if symbol.idx >= (part_number.range.0 - 1)
-                            && symbol.idx <= part_number.range.1
-                        {
-                            match gears.get_mut(&(-line_idx, symbol.idx)) {
-                                Some(gear) => gear.neighbours.push(part_number.clone()),
-                                None => (),
-                            }
-                            sum_part_numbers += part_number.number;
-                            validated_idxs.insert(part_number.range.0);
-                        }
-                    }
-                    previous_line_part_numbers
-                        .retain(|part_number| !validated_idxs.contains(&part_number.range.0));
-                    validated_idxs.clear();
-
-                    for part_number in current_line_part_numbers.iter() {
-                        if symbol.idx >= (part_number.range.0 - 1)
-                            && symbol.idx <= part_number.range.1
-                        {
-                            match gears.get_mut(&(-line_idx, symbol.idx)) {
-                                Some(gear) => gear.neighbours.push(part_number.clone()),
-                                None => (),
-                            }
-                            sum_part_numbers += part_number.number;
-                            validated_idxs.insert(part_number.range.0);
-                        }
-                    }
-                    current_line_part_numbers
-                        .retain(|part_number| !validated_idxs.contains(&part_number.range.0));
-                    validated_idxs.clear();
-                }
+impl Highlight {
+    fn ansi_color(&self) -> &'static str {
+        match self {
+            Highlight::PartNumber => "\x1b[32m",
+            Highlight::OrphanNumber => "\x1b[31m",
+            Highlight::Gear => "\x1b[1;33m",
+            Highlight::OtherSymbol => "\x1b[36m",
+        }
+    }
 
-                continue;
-            }
+    fn css_class(&self) -> &'static str {
+        match self {
+            Highlight::PartNumber => "part",
+            Highlight::OrphanNumber => "orphan",
+            Highlight::Gear => "gear",
+            Highlight::OtherSymbol => "symbol",
+        }
+    }
+}
+
+/// Numbers and symbols of the schematic, with the bipartite graph of which ones touch,
+/// diagonals included.
+#[derive(Debug)]
+struct Schematic {
+    lines: Vec<String>,
+    numbers: Vec<NumberSpan>,
+    symbols: Vec<Symbol>,
+    /// Symbols touching each number.
+    number_neighbours: Vec<Vec<usize>>,
+    /// Numbers touching each symbol.
+    symbol_neighbours: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    fn from_lines(input_lines: impl Iterator<Item = String>) -> Self {
+        let lines = input_lines.collect_vec();
+        let mut numbers = vec![];
+        let mut symbols = vec![];
 
-            let part_number = PartNumber::from_input_chars(&mut chars, idx);
-            if part_number.is_some() {
-                let part_number = part_number.unwrap();
-                idx = part_number.range.1; // Part number range is exclusive, so use the end index
-
-                let mut pending_validation = true;
-                for i in max(0, part_number.range.0 - 1)..(part_number.range.1 + 1) {
-                    if previous_line_symbol_idxs.contains(&i)
-                        || current_line_symbol_idxs.contains(&i)
-                    {
-                        if previous_line_symbol_idxs.contains(&i) {
-                            match gears.get_mut(&(-line_idx + 1, i)) {
-                                Some(gear) => gear.neighbours.push(part_number.clone()),
-                                None => (),
-                            }
-                        } else {
-                            match gears.get_mut(&(-line_idx, i)) {
-                                Some(gear) => gear.neighbours.push(part_number.clone()),
-                                None => (),
-                            }
-                        }
-
-                        sum_part_numbers += part_number.number;
-                        pending_validation = false;
-                        break;
+        for (row, line) in lines.iter().enumerate() {
+            let mut chars = line.chars().enumerate().peekable();
+            while let Some((column, c)) = chars.next() {
+                if let Some(digit) = c.to_digit(10) {
+                    let mut value = digit as u64;
+                    let mut end = column + 1;
+                    while let Some((_, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                        value = 10 * value + digit.to_digit(10).unwrap() as u64;
+                        end += 1;
                     }
+                    numbers.push(NumberSpan {
+                        value,
+                        row,
+                        columns: column..end,
+                    });
+                } else if c != '.' && !c.is_whitespace() {
+                    symbols.push(Symbol { kind: c, row, column });
                 }
+            }
+        }
 
-                if pending_validation {
-                    current_line_part_numbers.push(part_number);
-                }
+        let symbol_ids: HashMap<(usize, usize), usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(id, symbol)| ((symbol.row, symbol.column), id))
+            .collect();
 
-                continue;
+        let mut number_neighbours = vec![vec![]; numbers.len()];
+        let mut symbol_neighbours = vec![vec![]; symbols.len()];
+        for (id, number) in numbers.iter().enumerate() {
+            let rows = number.row.saturating_sub(1)..=number.row + 1;
+            let columns = number.columns.start.saturating_sub(1)..=number.columns.end;
+            for position in rows.cartesian_product(columns) {
+                if let Some(&symbol) = symbol_ids.get(&position) {
+                    number_neighbours[id].push(symbol);
+                    symbol_neighbours[symbol].push(id);
+                }
             }
         }
 
-        previous_line_symbol_idxs = current_line_symbol_idxs.drain(..).collect();
-        previous_line_part_numbers = current_line_part_numbers.drain(..).collect();
-        line_idx += 1;
+        Self {
+            lines,
+            numbers,
+            symbols,
+            number_neighbours,
+            symbol_neighbours,
+        }
     }
 
-    for (k, gear) in gears.iter() {
-        println!("{:?} - {:?}", k, gear);
-        if gear.neighbours.len() == 2 {
-            let gear_ratio = gear.neighbours[0].number * gear.neighbours[1].number;
-            sum_gear_ratios += gear_ratio;
-        }
+    /// Part numbers are the numbers touching at least one symbol.
+    fn sum_part_numbers(&self) -> u64 {
+        self.numbers
+            .iter()
+            .zip(self.number_neighbours.iter())
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number.value)
+            .sum()
     }
 
-    println!("Part A: {}", sum_part_numbers);
-    println!("Part B: {}", sum_gear_ratios);
-}
+    /// Symbols touching exactly `count` numbers, of the given kind or of any kind.
+    fn symbols_touching_exactly(&self, kind: Option<char>, count: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.symbols.len()).filter(move |&symbol| {
+            kind.is_none_or(|kind| self.symbols[symbol].kind == kind) && self.symbol_neighbours[symbol].len() == count
+        })
+    }
 
-#[derive(Clone, Copy, Debug)]
-struct PartNumber {
-    number: i32,
-    range: (i32, i32),
-}
+    fn neighbours_product(&self, symbol: usize) -> u64 {
+        self.symbol_neighbours[symbol].iter().map(|&number| self.numbers[number].value).product()
+    }
+
+    /// A gear is a `*` touching exactly two numbers, its ratio being their product.
+    fn sum_gear_ratios(&self) -> u64 {
+        self.symbols_touching_exactly(Some(GEAR), GEAR_NUMBERS)
+            .map(|symbol| self.neighbours_product(symbol))
+            .sum()
+    }
 
-impl PartNumber {
-    fn from_input_chars(input: &mut Peekable<Chars<'_>>, idx_offset: i32) -> Option<PartNumber> {
-        //  Exit early if first char is not a digit
-        if !input.peek().unwrap().is_digit(10) {
-            return None;
+    /// Sum of the numbers touching each kind of symbol. A number touching several symbols
+    /// counts once for each of them.
+    fn sum_per_symbol_kind(&self) -> BTreeMap<char, u64> {
+        let mut sums = BTreeMap::new();
+        for (symbol, numbers) in self.symbols.iter().zip(self.symbol_neighbours.iter()) {
+            *sums.entry(symbol.kind).or_insert(0) += numbers.iter().map(|&number| self.numbers[number].value).sum::<u64>();
         }
 
-        let mut number = 0;
-        let mut width = 0;
-        loop {
-            match input.next_if(|c| c.is_digit(10)) {
-                Some(digit) => {
-                    number = 10 * number + digit.to_digit(10).unwrap();
-                    width += 1;
-                }
-                None => break,
+        sums
+    }
+
+    fn is_gear(&self, symbol: usize) -> bool {
+        self.symbols[symbol].kind == GEAR && self.symbol_neighbours[symbol].len() == GEAR_NUMBERS
+    }
+
+    /// Highlight of every non empty cell, keyed by `(row, column)`.
+    fn highlights(&self) -> HashMap<(usize, usize), Highlight> {
+        let mut highlights = HashMap::new();
+
+        for (number, symbols) in self.numbers.iter().zip(self.number_neighbours.iter()) {
+            let highlight = if symbols.is_empty() { Highlight::OrphanNumber } else { Highlight::PartNumber };
+            for column in number.columns.clone() {
+                highlights.insert((number.row, column), highlight);
             }
         }
 
-        Some(PartNumber {
-            number: number as i32,
-            range: (idx_offset, idx_offset + width),
-        })
+        for (id, symbol) in self.symbols.iter().enumerate() {
+            let highlight = if self.is_gear(id) { Highlight::Gear } else { Highlight::OtherSymbol };
+            highlights.insert((symbol.row, symbol.column), highlight);
+        }
+
+        highlights
     }
-}
 
-#[derive(Clone, Debug)]
-struct Symbol {
-    idx: i32,
-    symbol_type: SymbolType,
-    neighbours: Vec<PartNumber>,
-}
+    /// Gear ratio annotations of each row, e.g. `* at 3: 467 x 35 = 16345`.
+    fn gear_annotations(&self) -> HashMap<usize, Vec<String>> {
+        let mut annotations: HashMap<usize, Vec<String>> = HashMap::new();
 
-#[derive(Clone, PartialEq, Debug)]
-enum SymbolType {
-    Gear,
-    Void,
-    Other,
-}
+        for (id, symbol) in self.symbols.iter().enumerate().filter(|&(id, _)| self.is_gear(id)) {
+            let factors = self.symbol_neighbours[id].iter().map(|&number| self.numbers[number].value).join(" x ");
+            annotations.entry(symbol.row).or_default().push(format!(
+                "{} at {}: {} = {}",
+                symbol.kind,
+                symbol.column,
+                factors,
+                self.neighbours_product(id)
+            ));
+        }
 
-fn next_symbol_idx(input: &mut Peekable<Chars<'_>>, idx_offset: i32) -> Option<Symbol> {
-    // Exit early if first char is a digit
-    if input.peek().unwrap().is_digit(10) {
-        return None;
-    }
-
-    let mut idx = 0;
-    let mut symbol_type = SymbolType::Other;
-    loop {
-        match input.next_if(|c| !c.is_digit(10)) {
-            Some(c) => {
-                idx += 1;
-                match c {
-                    '*' => {
-                        symbol_type = SymbolType::Gear;
-                        break;
-                    }
-                    '.' => symbol_type = SymbolType::Void,
-                    _ => {
-                        symbol_type = SymbolType::Other;
-                        break;
+        annotations
+    }
+
+    /// The schematic with a distinct color per highlight, and gear ratios at the end of their row.
+    fn render_ansi(&self) -> String {
+        let highlights = self.highlights();
+        let annotations = self.gear_annotations();
+        let mut output = String::new();
+
+        for (row, line) in self.lines.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                let color = highlights.get(&(row, column)).map_or(ANSI_DIM, Highlight::ansi_color);
+                output.push_str(&format!("{}{}{}", color, c, ANSI_RESET));
+            }
+            if let Some(annotations) = annotations.get(&row) {
+                let color = Highlight::Gear.ansi_color();
+                output.push_str(&format!("  {}{}{}", color, annotations.join(", "), ANSI_RESET));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Same as `render_ansi` as a standalone HTML page, gears also showing their ratio on hover.
+    fn render_html(&self) -> String {
+        let highlights = self.highlights();
+        let annotations = self.gear_annotations();
+        let gear_titles: HashMap<(usize, usize), u64> = (0..self.symbols.len())
+            .filter(|&id| self.is_gear(id))
+            .map(|id| ((self.symbols[id].row, self.symbols[id].column), self.neighbours_product(id)))
+            .collect();
+
+        let mut body = String::new();
+        for (row, line) in self.lines.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                let c = escape_html(c);
+                match (highlights.get(&(row, column)), gear_titles.get(&(row, column))) {
+                    (Some(highlight), Some(ratio)) => body.push_str(&format!(
+                        "<span class=\"{}\" title=\"ratio {}\">{}</span>",
+                        highlight.css_class(),
+                        ratio,
+                        c
+                    )),
+                    (Some(highlight), None) => {
+                        body.push_str(&format!("<span class=\"{}\">{}</span>", highlight.css_class(), c))
                     }
+                    (None, _) => body.push_str(&c),
                 }
             }
-            None => break,
+            if let Some(annotations) = annotations.get(&row) {
+                body.push_str(&format!("  <span class=\"ratio\">{}</span>", annotations.join(", ")));
+            }
+            body.push('\n');
         }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Engine schematic</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<pre>\n{}</pre>\n</body>\n</html>\n",
+            HTML_STYLE, body
+        )
     }
+}
 
-    Some(Symbol {
-        idx: idx_offset + idx - 1,
-        symbol_type,
-        neighbours: Vec::<PartNumber>::new(),
-    })
+fn escape_html(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        '"' => "&quot;".to_string(),
+        c => c.to_string(),
+    }
 }
@@ -1,70 +1,57 @@
-use std::collections::HashSet;
-
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::{
+    scratchcards::{play_scratchcards, CardOutcome, ScoringRule, Scratchcard},
+    utils::{get_aoc_input_lines, get_flag_values, has_flag},
+};
+use itertools::Itertools;
 
 fn main() {
+    let scoring_rule = get_flag_values("--scoring", 1)
+        .map_or(Ok(ScoringRule::Doubling), |values| values[0].parse::<ScoringRule>())
+        .expect("Error parsing scoring rule");
+
     let input_lines = get_aoc_input_lines().expect("Error getting input");
-    let mut points = 0;
-    let mut card_counter = Vec::with_capacity(100);
+    let cards = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.parse::<Scratchcard>().expect("Error parsing card"))
+        .collect_vec();
 
-    for (idx, line) in input_lines.enumerate() {
-        let line = line.expect("Error reading line");
-        
-        let card = parse_line(&line);
-        let intersection = card.expected_numbers.intersection(&card.numbers);
-        let matches = intersection.count();
+    let outcomes = play_scratchcards(&cards);
 
-        card_counter.put_mod(idx, |x| x + 1);
-        let next_cards_coeff = card_counter[idx];
-        for i in 1..matches + 1 {
-            card_counter.put_mod(idx + i, |x| x + next_cards_coeff);
-        }
-
-        if matches > 0 {
-            points += usize::pow(2, (matches - 1) as u32);
+    if has_flag("--breakdown") {
+        for outcome in outcomes.iter() {
+            println!("{}", describe_outcome(outcome, &outcomes, scoring_rule));
         }
     }
 
-    println!("Points: {}", points);
-    println!("Scratch cards: {}", card_counter.iter().sum::<usize>());
-}
-
-struct ScratchCard {
-    expected_numbers: HashSet<usize>,
-    numbers: HashSet<usize>,
-}
+    let points: u64 = outcomes.iter().map(|outcome| scoring_rule.score(outcome.matches)).sum();
+    let copies: u64 = outcomes.iter().map(|outcome| outcome.copies).sum();
 
-fn parse_line(line: &str) -> ScratchCard {
-    let mut parts = line.split(":").last().expect("Error splittng Card id").split("|");
-    let expected_numbers = parse_numbers_to_hashset(parts.next().expect("Error parsing expected numbers").trim());
-    let numbers = parse_numbers_to_hashset(parts.next().expect("Error parsing my numbers").trim());
-
-    ScratchCard {
-        expected_numbers,
-        numbers,
-    }
-}
-
-fn parse_numbers_to_hashset(numbers: &str) -> HashSet<usize> {
-    let mut result = HashSet::new();
-
-    for number in numbers.split_whitespace() {
-        result.insert(number.parse::<usize>().expect("Error parsing number"));
-    }
-
-    result
+    println!("Points: {}", points);
+    println!("Scratch cards: {}", copies);
 }
 
-trait VecPut<T> {
-    fn put_mod<F: Fn(T) -> T>(&mut self, index: usize, modifer: F);
+fn describe_outcome(outcome: &CardOutcome, outcomes: &[CardOutcome], scoring_rule: ScoringRule) -> String {
+    let wins = match (outcome.wins.first(), outcome.wins.last()) {
+        (Some(&first), Some(&last)) => format!("wins cards {}-{}", outcomes[first].id, outcomes[last].id),
+        _ => "wins nothing".to_string(),
+    };
+    let spawned_by = match outcome.spawned_by.is_empty() {
+        true => "none".to_string(),
+        false => outcome
+            .spawned_by
+            .iter()
+            .map(|&(parent, copies)| format!("card {} ({})", outcomes[parent].id, copies))
+            .join(", "),
+    };
+
+    format!(
+        "Card {}: {} matches, {} points, {} instances, {}, copies won from: {}",
+        outcome.id,
+        outcome.matches,
+        scoring_rule.score(outcome.matches),
+        outcome.copies,
+        wins,
+        spawned_by
+    )
 }
-
-impl<T> VecPut<T> for Vec<T> where T: Clone, T: Default {
-    fn put_mod<F: Fn(T) -> T>(&mut self, index: usize, modifier: F) {
-        if self.len() <= index {
-            self.resize(index + 1, modifier(Default::default()));
-        } else {
-            self[index] = modifier(self[index].clone());
-        }
-    }
-}
\ No newline at end of file
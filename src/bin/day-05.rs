@@ -1,97 +1,213 @@
-use std::{io::{Lines, BufReader}, fs::File, collections::HashSet};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::Range,
+};
 
-use advent_of_code_2023::utils::get_aoc_input_lines;
-use itertools::min;
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values, RangeMap};
+use itertools::Itertools;
 
-fn main() {
-    let mut input_lines = get_aoc_input_lines().expect("Error getting input");
-    let mut seeds: Vec<(i64, i64)> = Vec::new();
+const SEED: &str = "seed";
+const LOCATION: &str = "location";
 
-    while let Some(line) = input_lines.next() {
-        let line = line.expect("Error getting line");
+// Header categories and ranges of a map section, as read
+type Section = (String, String, Vec<(i64, i64, i64)>);
 
-        if line.starts_with("seeds:") {
-            seeds = process_seeds(&line);
-            continue;
-        }
+fn main() {
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let almanac = Almanac::from_lines(input_lines.map(|line| line.expect("Error reading line")));
 
-        if line.ends_with("map:") {
-            seeds = process_map(seeds.clone(), &mut input_lines);
-        }
+    for warning in almanac.find_overlaps() {
+        eprintln!("Warning: {}", warning);
     }
 
-    println!("seeds: {:?}", min(seeds).unwrap().0);
+    // `--map FROM TO VALUE`: follow a value from a category to another, backwards if needed
+    if let Some(values) = get_flag_values("--map", 3) {
+        let (from, to, value) = values.iter().collect_tuple().expect("Error parsing --map, expected FROM TO VALUE");
+        let value = value.parse::<i64>().expect("Error parsing --map value");
+        println!("{}", almanac.describe_mapping(from, to, value));
+    }
+
+    // Every layer between seeds and locations collapses into a single map
+    let seed_to_location = almanac
+        .compose(SEED, LOCATION)
+        .expect("Error finding a chain of maps from seed to location");
+
+    let seeds = almanac.seeds.iter().map(|&seed| seed..seed + 1).collect_vec();
+    let seed_ranges = almanac
+        .seeds
+        .iter()
+        .tuples()
+        .map(|(&start, &length)| start..start + length)
+        .collect_vec();
+
+    println!("Lowest location - Part 1: {}", lowest_location(&seed_to_location, &seeds));
+    println!("Lowest location for seed ranges - Part 2: {}", lowest_location(&seed_to_location, &seed_ranges));
+}
+
+fn lowest_location(seed_to_location: &RangeMap, seeds: &[Range<i64>]) -> i64 {
+    seed_to_location
+        .map_intervals(seeds)
+        .first()
+        .expect("Error finding a location, no seeds given")
+        .start
 }
 
-fn process_seeds(line: &str) -> Vec<(i64, i64)> {
-    let line = line.split(":").last().expect("Error splitting seeds");
-    let line = line.trim();
+/// A `<from>-to-<to> map:` section of the almanac.
+#[derive(Debug)]
+struct Layer {
+    from: String,
+    to: String,
+    /// `(destination, source, length)` as written in the almanac.
+    ranges: Vec<(i64, i64, i64)>,
+    map: RangeMap,
+}
 
-    line.split_whitespace().map(|seed| {
-        seed.parse::<i64>().expect("Error parsing seed")
-    }).collect::<Vec<_>>().chunks(2).map(|pair| {
-        (pair[0], pair[1])
-    }).collect::<Vec<_>>()
+/// Graph of categories, each layer mapping a category to another.
+#[derive(Debug)]
+struct Almanac {
+    seeds: Vec<i64>,
+    layers: Vec<Layer>,
 }
 
-fn process_map(seeds: Vec<(i64, i64)>, input_lines: &mut Lines<BufReader<File>>) -> Vec<(i64, i64)> {
-    let mut confirmed_seeds = HashSet::new();
-    let mut seeds = seeds;
-    let map_lines = input_lines.take_while(|line| {
-        let line = line.as_ref().expect("Error getting line while processing map");
-        !line.trim().is_empty()
-    }).collect::<Vec<_>>();
-
-    let mut previous_seeds = seeds.clone();
-    loop {
-        for line in map_lines.iter() {
-            let line = line.as_ref().expect("Error getting line while processing map");
-    
-            let (destination, source, length) = process_map_line(&line);
-            seeds = seeds.iter().flat_map(|seed| {
-                let seed = *seed;
-    
-                if seed.0 >= source && seed.0 < source + length && !confirmed_seeds.contains(&seed) {
-                    let new_seed_source = seed.0 + destination - source;
-                    let covered_count = length - (seed.0 - source);
-                    if covered_count >= seed.1 {
-                        confirmed_seeds.insert((new_seed_source, seed.1));
-                        return vec![(new_seed_source, seed.1)];
-                    } else {
-                        confirmed_seeds.insert((new_seed_source, covered_count));
-                        return vec![(new_seed_source, covered_count), (seed.0 + covered_count, seed.1 - covered_count)];
-                    }
-                } else if source > seed.0 && source < seed.0 + seed.1 && !confirmed_seeds.contains(&seed) {
-                    let available_length = seed.0 + seed.1 - source;
-                    let map_length = length;
-                    let covered_count = std::cmp::min(available_length, map_length);
-                    if available_length <= map_length {
-                        confirmed_seeds.insert((destination, covered_count));
-                        return vec![(seed.0, seed.1 - covered_count), (destination, covered_count)];
-                    } else if available_length > map_length {
-                        confirmed_seeds.insert((destination, covered_count));
-                        return vec![(seed.0, seed.1 - covered_count), (destination, covered_count), (source + covered_count, available_length - map_length)];
-                    }
+impl Almanac {
+    fn from_lines(input_lines: impl Iterator<Item = String>) -> Self {
+        let mut seeds = vec![];
+        let mut sections: Vec<Section> = vec![];
+
+        for line in input_lines {
+            let line = line.trim();
+
+            if let Some(line) = line.strip_prefix("seeds:") {
+                seeds = line
+                    .split_whitespace()
+                    .map(|seed| seed.parse::<i64>().expect("Error parsing seed"))
+                    .collect();
+            } else if let Some(header) = line.strip_suffix("map:") {
+                let (from, to) = header
+                    .trim()
+                    .split("-to-")
+                    .collect_tuple()
+                    .expect("Error parsing map header, expected <from>-to-<to> map:");
+                sections.push((from.to_string(), to.to_string(), vec![]));
+            } else if !line.is_empty() {
+                let (_, _, ranges) = sections.last_mut().expect("Error parsing map range outside of a map");
+                ranges.push(parse_range(line));
+            }
+        }
+
+        let layers = sections
+            .into_iter()
+            .map(|(from, to, ranges)| Layer {
+                map: RangeMap::from_ranges(&ranges),
+                from,
+                to,
+                ranges,
+            })
+            .collect();
+
+        Self { seeds, layers }
+    }
+
+    /// Shortest chain of layers leading from a category to another.
+    fn find_chain(&self, from: &str, to: &str) -> Option<Vec<&Layer>> {
+        let mut previous_layer: HashMap<&str, &Layer> = HashMap::new();
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut chain = vec![];
+                let mut category = to;
+                while let Some(layer) = previous_layer.get(category) {
+                    chain.push(*layer);
+                    category = &layer.from;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+
+            for layer in self.layers.iter().filter(|layer| layer.from == category) {
+                if visited.insert(&layer.to) {
+                    previous_layer.insert(&layer.to, layer);
+                    queue.push_back(&layer.to);
                 }
-    
-                vec![seed]
-            }).collect();
+            }
+        }
+
+        None
+    }
+
+    fn compose(&self, from: &str, to: &str) -> Option<RangeMap> {
+        let chain = self.find_chain(from, to)?;
+        Some(chain.iter().fold(RangeMap::identity(), |map, layer| map.then(&layer.map)))
+    }
+
+    /// Maps a value forward when `to` can be reached from `from`, otherwise looks for the
+    /// values of `from` landing on it by going through the chain from `to` backwards.
+    fn describe_mapping(&self, from: &str, to: &str, value: i64) -> String {
+        if let Some(map) = self.compose(from, to) {
+            return format!("{} {} -> {} {}", from, value, to, map.get(value));
         }
 
-        if previous_seeds == seeds {
-            break;
+        let Some(map) = self.compose(to, from) else {
+            return format!("No chain of maps between {} and {}", from, to);
+        };
+        let preimage = map.preimage(value);
+        if preimage.is_empty() {
+            return format!("No {} lands on {} {}", to, from, value);
         }
-        previous_seeds = seeds.clone();
+
+        preimage
+            .iter()
+            .map(|&source| {
+                let is_seed = to == SEED && self.seeds.contains(&source);
+                let is_in_seed_range = to == SEED
+                    && self
+                        .seeds
+                        .iter()
+                        .tuples()
+                        .any(|(&start, &length)| (start..start + length).contains(&source));
+                let note = match (is_seed, is_in_seed_range) {
+                    (true, true) => " (listed seed, in a seed range)",
+                    (true, false) => " (listed seed)",
+                    (false, true) => " (in a seed range)",
+                    (false, false) => "",
+                };
+                format!("{} {} <- {} {}{}", from, value, to, source, note)
+            })
+            .join("\n")
     }
 
-    seeds
-}
+    /// Source ranges overlapping within a layer, where only the first one applies.
+    fn find_overlaps(&self) -> Vec<String> {
+        let mut overlaps = vec![];
+
+        for layer in self.layers.iter() {
+            let sources = layer
+                .ranges
+                .iter()
+                .map(|&(_, source, length)| source..source + length)
+                .filter(|source| !source.is_empty())
+                .sorted_by_key(|source| source.start)
+                .collect_vec();
 
-fn process_map_line(line: &str) -> (i64, i64, i64) {
-    let mut line = line.split_whitespace();
-    let destination = line.next().expect("Error getting destination").parse::<i64>().expect("Error parsing destination");
-    let source = line.next().expect("Error getting source").parse::<i64>().expect("Error parsing source");
-    let length = line.next().expect("Error getting length").parse::<i64>().expect("Error parsing length");
+            for (a, b) in sources.iter().tuple_combinations() {
+                if b.start < a.end {
+                    overlaps.push(format!(
+                        "{}-to-{} map: source ranges {:?} and {:?} overlap",
+                        layer.from, layer.to, a, b
+                    ));
+                }
+            }
+        }
 
-    (destination, source, length)
-}
\ No newline at end of file
+        overlaps
+    }
+}
+
+fn parse_range(line: &str) -> (i64, i64, i64) {
+    line.split_whitespace()
+        .map(|value| value.parse::<i64>().expect("Error parsing map range"))
+        .collect_tuple()
+        .expect("Error parsing map range, expected destination, source and length")
+}
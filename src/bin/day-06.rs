@@ -1,35 +1,69 @@
 use advent_of_code_2023::utils::get_aoc_input_lines;
+use itertools::Itertools;
+use num::{integer::Roots, BigInt, Integer};
 
 fn main() {
-    let mut input_lines = get_aoc_input_lines().unwrap();
-    let times = parse_line(&input_lines.next().unwrap().unwrap());
-    let records = parse_line(&input_lines.next().unwrap().unwrap());
-    let power = times.iter().zip(records.iter()).map(|(x, y)| {
-        get_winning_distances_count(*x, *y)
-    }).fold(1, |acc, x| acc * x);
-    println!("Power: {}", power);
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let lines = input_lines.map(|line| line.expect("Error reading line")).collect_vec();
+    let find_line = |header: &str| {
+        lines
+            .iter()
+            .find_map(|line| line.trim().strip_prefix(header))
+            .unwrap_or_else(|| panic!("Error finding {} line", header))
+    };
+    let (times, records) = (find_line("Time:"), find_line("Distance:"));
+
+    // Each column is a race
+    let races = parse_numbers(times).into_iter().zip(parse_numbers(records)).collect_vec();
+    let ways_to_win: BigInt = races.iter().map(|(time, record)| count_winning_holds(time, record)).product();
+    println!("Ways to win product - Part 1: {}", ways_to_win);
+
+    // The spaces are a bad kerning of a single race
+    let (time, record) = (parse_kerned_number(times), parse_kerned_number(records));
+    println!("Ways to win the kerned race - Part 2: {}", count_winning_holds(&time, &record));
+}
+
+fn parse_numbers(line: &str) -> Vec<BigInt> {
+    line.split_whitespace()
+        .map(|number| number.parse::<BigInt>().expect("Error parsing number"))
+        .collect()
+}
+
+fn parse_kerned_number(line: &str) -> BigInt {
+    line.split_whitespace()
+        .collect::<String>()
+        .parse::<BigInt>()
+        .expect("Error parsing kerned number")
 }
 
-fn parse_line(line: &str) -> Vec<i64> {
-    let line = line.split(":").last().expect("Error parsing line").trim();
-    let line = line.replace(" ", "");
-    line.split_whitespace().map(|x| x.parse::<i64>().expect("Error parsing line")).collect()
+/// Works on `i128` while the race fits in 64 bits, as squaring the time cannot overflow then,
+/// and on big integers otherwise.
+fn count_winning_holds(time: &BigInt, record: &BigInt) -> BigInt {
+    match (i64::try_from(time), i64::try_from(record)) {
+        (Ok(time), Ok(record)) => count_winning_holds_in(time as i128, record as i128).into(),
+        _ => count_winning_holds_in(time.clone(), record.clone()),
+    }
 }
 
-fn get_winning_distances_count(time: i64, record: i64) -> i64 {
-    let mut count = 0;
-    let mut we_are_now_above = false;
-    for i in 0..time {
-        let distance = i * (time - i);
-        if distance > record {
-            count += 1;
-            we_are_now_above = true;
-        } else if we_are_now_above {
-            break;
-        }
+/// Holding the button for `hold` ms makes the boat travel `hold * (time - hold)`, which beats
+/// the record strictly between the roots of `hold^2 - time * hold + record`. The integer square
+/// root of the discriminant gives the lower root up to rounding, which is then fixed by checking
+/// its neighbours. The winning holds are symmetric around `time / 2`.
+fn count_winning_holds_in<T: Integer + Roots + Clone + From<i64>>(time: T, record: T) -> T {
+    let distance = |hold: &T| hold.clone() * (time.clone() - hold.clone());
+    if distance(&(time.clone() / T::from(2))) <= record {
+        return T::zero();
     }
 
-    println!("Time: {}, Record: {}, Count: {}", time, record, count);
+    let discriminant = time.clone() * time.clone() - T::from(4) * record.clone();
+    let mut low = (time.clone() - discriminant.sqrt()) / T::from(2);
+    while distance(&low) <= record {
+        low = low + T::one();
+    }
+    while low > T::zero() && distance(&(low.clone() - T::one())) > record {
+        low = low - T::one();
+    }
 
-    count
-}
\ No newline at end of file
+    let high = time - low.clone();
+    high - low + T::one()
+}
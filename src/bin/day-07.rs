@@ -1,40 +1,70 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap, fs};
 
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values};
 use itertools::Itertools;
+use serde::Deserialize;
+
+// Ranking used when no `--ranking FILE` is given
+const STANDARD_RANKING: &str = include_str!("../../rankings/standard.toml");
 
 fn main() {
+    let wildcard = get_flag_values("--wildcard", 1).map(|values| Card::from_str(&values[0]));
+    let ranking = match get_flag_values("--ranking", 1) {
+        Some(values) => HandRanking::from_toml(&fs::read_to_string(&values[0]).expect("Error reading hand ranking")),
+        None => HandRanking::from_toml(STANDARD_RANKING),
+    };
+
+    let mut rule_sets = vec![RuleSet::StandardJacks, RuleSet::JokersWild];
+    rule_sets.extend(wildcard.map(RuleSet::Wildcard));
+
+    // `--explain CARDS CARDS`: why a hand outranks another under each rule set
+    if let Some(values) = get_flag_values("--explain", 2) {
+        let (a, b) = (Hand::from_cards(&values[0]), Hand::from_cards(&values[1]));
+        for &rules in rule_sets.iter() {
+            println!("{:?}: {}", rules, a.explain_against(&b, rules, &ranking));
+        }
+    }
+
     let input_lines = get_aoc_input_lines().expect("Error getting input");
     let hands = input_lines
-        .map(|line| Hand::from_str(&line.expect("Error reading input line")))
-        .sorted_by_key(|hand| (hand.hand_type, hand.cards.clone()))
+        .map(|line| line.expect("Error reading input line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Hand::from_str(&line))
+        .collect_vec();
+
+    if let Some(wildcard) = wildcard {
+        let winnings = total_winnings(&hands, RuleSet::Wildcard(wildcard), &ranking);
+        println!("Total bid with {:?} wild: {}", wildcard, winnings);
+    }
+
+    println!("Total bid - Part 1: {}", total_winnings(&hands, RuleSet::StandardJacks, &ranking));
+    println!("Total bid with jokers - Part 2: {}", total_winnings(&hands, RuleSet::JokersWild, &ranking));
+}
+
+fn total_winnings(hands: &[Hand], rules: RuleSet, ranking: &HandRanking) -> usize {
+    hands
+        .iter()
+        .sorted_by_cached_key(|hand| hand.sort_key(rules, ranking))
         .enumerate()
-        .inspect(|hand| println!("{:?}", hand))
         .fold(0, |acc, (i, hand)| {
             let rank = i + 1;
             acc + hand.bid * rank
-        });
-
-    println!("Total bid: {}", hands);
+        })
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
-#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Card {
-    Joker = 0,
     Number(u8),
-    Jack = 11,
-    Queen = 12,
-    King = 13,
-    Ace = 14,
+    Jack,
+    Queen,
+    King,
+    Ace,
 }
 
 impl Card {
-    #[allow(unreachable_patterns)]
     fn from_str(s: &str) -> Card {
         match s {
-            "J" => Card::Joker,
-            "J" => Card::Jack, // Used in Part 1, unreachable in Part 2
+            "J" => Card::Jack,
             "Q" => Card::Queen,
             "K" => Card::King,
             "A" => Card::Ace,
@@ -42,87 +72,194 @@ impl Card {
             _ => Card::Number(s.parse::<u8>().expect("Error parsing card value")),
         }
     }
+
+    fn label(&self) -> char {
+        match self {
+            Card::Number(10) => 'T',
+            Card::Number(value) => char::from_digit(*value as u32, 10).expect("Error formatting card value"),
+            Card::Jack => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
+        }
+    }
+
+    /// Strength of the card when it is not wild, from 2 to 14.
+    fn value(&self) -> u8 {
+        match self {
+            Card::Number(value) => *value,
+            Card::Jack => 11,
+            Card::Queen => 12,
+            Card::King => 13,
+            Card::Ace => 14,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-#[repr(u8)]
-enum HandType {
-    FiveOfAKind = 6, // Strongest
-    FourOfAKind = 5,
-    FullHouse = 4,
-    ThreeOfAKind = 3,
-    TwoPair = 2,
-    OnePair = 1,
-    HighCard = 0, // Weakest
+/// Which card, if any, is wild. A wild card joins the most common other card to make the
+/// strongest hand, but is the weakest card when breaking ties.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum RuleSet {
+    StandardJacks,
+    JokersWild,
+    Wildcard(Card),
 }
 
-impl HandType {
-    fn from_cards(cards: &Vec<Card>) -> HandType {
-        let mut counter = HashMap::new();
-        for card in cards {
-            *counter.entry(card).or_insert(0) += 1;
+impl RuleSet {
+    fn wildcard(&self) -> Option<Card> {
+        match self {
+            RuleSet::StandardJacks => None,
+            RuleSet::JokersWild => Some(Card::Jack),
+            RuleSet::Wildcard(card) => Some(*card),
         }
+    }
 
-        // Add jokers to most common card
-        let jokers = *counter.get(&Card::Joker).unwrap_or(&0);
-        if jokers > 0 && jokers < 5 {
-            counter.remove(&Card::Joker);
-            let max_card = counter
-                .iter()
-                .max_by_key(|(_, &v)| v)
-                .expect("Error getting max card").0;
-            *counter.entry(&max_card).or_insert(0) += jokers;
+    fn strength(&self, card: Card) -> u8 {
+        if self.wildcard() == Some(card) {
+            0
+        } else {
+            card.value()
         }
+    }
+}
 
-        if counter.len() == 5 {
-            HandType::HighCard
-        } else if counter.len() == 4 {
-            HandType::OnePair
-        } else if counter.len() == 3 {
-            if counter.values().any(|&v| v == 3) {
-                HandType::ThreeOfAKind
-            } else {
-                HandType::TwoPair
-            }
-        } else if counter.len() == 2 {
-            if counter.values().any(|&v| v == 4) {
-                HandType::FourOfAKind
-            } else {
-                HandType::FullHouse
-            }
-        } else {
-            HandType::FiveOfAKind
+/// How many times each distinct card of a hand appears, largest first, e.g. `[3, 2]` for a full
+/// house. Wild cards join the largest count.
+type Shape = Vec<usize>;
+
+fn shape_of(cards: &[Card], rules: RuleSet) -> Shape {
+    let mut counter = HashMap::new();
+    for card in cards.iter().filter(|&&card| rules.wildcard() != Some(card)) {
+        *counter.entry(*card).or_insert(0) += 1;
+    }
+
+    let wildcards = cards.len() - counter.values().sum::<usize>();
+    let mut shape = counter.into_values().sorted().rev().collect_vec();
+    match shape.first_mut() {
+        Some(largest) => *largest += wildcards,
+        None => shape.push(wildcards),
+    }
+
+    shape
+}
+
+fn format_shape(shape: &Shape) -> String {
+    shape.iter().join("+")
+}
+
+#[derive(Debug, Deserialize)]
+struct Category {
+    name: String,
+    shape: Shape,
+}
+
+/// Hand categories from weakest to strongest.
+#[derive(Debug, Deserialize)]
+struct HandRanking {
+    #[serde(rename = "category")]
+    categories: Vec<Category>,
+}
+
+impl HandRanking {
+    fn from_toml(s: &str) -> HandRanking {
+        let mut ranking: HandRanking = toml::from_str(s).expect("Error parsing hand ranking");
+        for category in ranking.categories.iter_mut() {
+            category.shape.sort_unstable_by(|a, b| b.cmp(a));
         }
+
+        ranking
     }
+
+    /// Position of the category of the shape, from 1 for the weakest, or 0 when no category
+    /// lists it. Shapes of a same rank are then compared count by count, which for unlisted
+    /// shapes matches the usual order whatever the hand size.
+    fn rank(&self, shape: &Shape) -> usize {
+        self.categories
+            .iter()
+            .position(|category| category.shape == *shape)
+            .map_or(0, |i| i + 1)
+    }
+
+    fn name(&self, shape: &Shape) -> String {
+        self.categories
+            .iter()
+            .find(|category| category.shape == *shape)
+            .map_or_else(|| format!("Unranked {}", format_shape(shape)), |category| category.name.clone())
+    }
+}
+
+fn parse_cards(s: &str) -> Vec<Card> {
+    s.chars().map(|c| Card::from_str(&c.to_string())).collect()
+}
+
+fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(|card| card.label()).collect()
 }
 
 #[derive(Debug)]
 struct Hand {
     cards: Vec<Card>,
     bid: usize,
-    hand_type: HandType,
 }
 
 impl Hand {
     fn from_str(s: &str) -> Hand {
-        let mut s = s.trim().split_whitespace();
-        let cards: Vec<Card> = s
-            .next()
-            .expect("Error parsing cards from hand line")
-            .chars()
-            .map(|c| Card::from_str(&c.to_string()))
-            .collect();
+        let mut s = s.split_whitespace();
+        let cards = parse_cards(s.next().expect("Error parsing cards from hand line"));
         let bid = s
             .next()
             .expect("Error parsing bid from hand line")
             .parse::<usize>()
             .expect("Error parsing bid from hand line");
-        let hand_type = HandType::from_cards(&cards);
 
-        Hand {
-            cards,
-            bid,
-            hand_type,
-        }
+        Hand { cards, bid }
+    }
+
+    fn from_cards(s: &str) -> Hand {
+        Hand { cards: parse_cards(s), bid: 0 }
+    }
+
+    /// Hands are ranked by category, then by shape, then card by card.
+    fn sort_key(&self, rules: RuleSet, ranking: &HandRanking) -> (usize, Shape, Vec<u8>) {
+        let shape = shape_of(&self.cards, rules);
+        let strengths = self.cards.iter().map(|&card| rules.strength(card)).collect();
+        (ranking.rank(&shape), shape, strengths)
+    }
+
+    fn describe(&self, rules: RuleSet, ranking: &HandRanking) -> String {
+        let shape = shape_of(&self.cards, rules);
+        format!("{} ({}, {})", format_cards(&self.cards), ranking.name(&shape), format_shape(&shape))
+    }
+
+    /// Why this hand wins, loses or ties against another one.
+    fn explain_against(&self, other: &Hand, rules: RuleSet, ranking: &HandRanking) -> String {
+        let (key, other_key) = (self.sort_key(rules, ranking), other.sort_key(rules, ranking));
+        let (winner, loser, winner_key, loser_key) = match key.cmp(&other_key) {
+            Ordering::Equal => {
+                return format!("{} ties with {}", self.describe(rules, ranking), other.describe(rules, ranking))
+            }
+            Ordering::Greater => (self, other, &key, &other_key),
+            Ordering::Less => (other, self, &other_key, &key),
+        };
+
+        let reason = if winner_key.0 != loser_key.0 {
+            "its category ranks higher".to_string()
+        } else if winner_key.1 != loser_key.1 {
+            format!("its shape {} beats {}", format_shape(&winner_key.1), format_shape(&loser_key.1))
+        } else {
+            let i = (0..winner.cards.len())
+                .find(|&i| winner_key.2[i] != loser_key.2[i])
+                .expect("Error finding the card breaking the tie");
+            format!(
+                "card {} is {} ({}) against {} ({})",
+                i + 1,
+                format_cards(&winner.cards[i..=i]),
+                winner_key.2[i],
+                format_cards(&loser.cards[i..=i]),
+                loser_key.2[i]
+            )
+        };
+
+        format!("{} beats {}: {}", winner.describe(rules, ranking), loser.describe(rules, ranking), reason)
     }
 }
@@ -1,6 +1,13 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, process};
 
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::utils::{combine_congruences, get_aoc_input_lines, get_flag_values};
+use itertools::Itertools;
+
+const START_NODE: &str = "AAA";
+const END_NODE: &str = "ZZZ";
+
+// Colors of the ghost loops in the DOT export, reused when there are more ghosts
+const GHOST_COLORS: [&str; 6] = ["blue", "orange", "purple", "brown", "deeppink", "darkcyan"];
 
 fn main() {
     let mut input_lines = get_aoc_input_lines().expect("Error getting input");
@@ -21,41 +28,209 @@ fn main() {
         })
         .collect();
 
-    let mut lcm = 1;
-    let instructions_count = instructions.len();
-    let mut steps_count = 0;
-    let mut current_nodes = node_map
-        .iter()
-        .filter(|(_, node)| node.is_start_node())
-        .map(|(_, node)| node)
-        .collect::<Vec<&Node>>();
-    loop {
-        let instruction = instructions
-            .get(steps_count % instructions_count)
-            .expect("Error getting instruction");
-        steps_count += 1;
-
-        current_nodes = current_nodes
-            .iter()
-            .map(|node| node_map.get(node.get_next_node(instruction)).expect("Error getting next node"))
-            .collect::<Vec<&Node>>();
+    let network = Network { instructions, node_map };
+    let ghosts = network
+        .node_map
+        .values()
+        .filter(|node| node.is_start_node())
+        .map(|node| network.trace_ghost(&node.key, Node::is_end_node))
+        .sorted_by(|a, b| a.start.cmp(&b.start))
+        .collect_vec();
+
+    if let Some(values) = get_flag_values("--dot", 1) {
+        fs::write(&values[0], network.to_dot(&ghosts)).expect("Error writing DOT file");
+    }
+
+    // The ghost examples have no `AAA` node to walk from
+    if network.node_map.contains_key(START_NODE) {
+        let walk = network.trace_ghost(START_NODE, |node| node.key == END_NODE);
+        match walk.first_hit() {
+            Some(steps) => println!("Steps from {} to {} - Part 1: {}", START_NODE, END_NODE, steps),
+            None => {
+                eprintln!("Error: {} is never reached from {}", END_NODE, START_NODE);
+                process::exit(1);
+            }
+        }
+    }
+
+    match find_sync(&ghosts) {
+        Some(steps) => println!("Steps until all ghosts are on end nodes - Part 2: {}", steps),
+        None => {
+            eprintln!("Error: the ghosts are never all on end nodes at the same step");
+            for ghost in ghosts.iter() {
+                eprintln!("  {}", ghost.describe());
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// The instructions and the nodes they lead through.
+#[derive(Debug)]
+struct Network {
+    instructions: Vec<Instruction>,
+    node_map: HashMap<String, Node>,
+}
+
+impl Network {
+    /// Walks from `start` until the ghost is back on a node at the same instruction index,
+    /// from where it can only repeat itself, noting when it is on a node matching `is_end`.
+    fn trace_ghost(&self, start: &str, is_end: impl Fn(&Node) -> bool) -> GhostCycle {
+        let mut first_visits: HashMap<(&str, usize), usize> = HashMap::new();
+        let mut path = vec![];
+        let mut node = self.node_map.get(start).expect("Error getting start node");
+
+        for step in 0.. {
+            let instruction_index = step % self.instructions.len();
+            if let Some(&prefix) = first_visits.get(&(node.key.as_str(), instruction_index)) {
+                return GhostCycle::from_path(path, prefix, is_end);
+            }
+            first_visits.insert((node.key.as_str(), instruction_index), step);
+            path.push(node);
+
+            let next = node.get_next_node(&self.instructions[instruction_index]);
+            node = self.node_map.get(next).expect("Error getting next node");
+        }
+
+        unreachable!()
+    }
+
+    /// The network as a Graphviz digraph, with start nodes in green, end nodes in red, `AAA`
+    /// and `ZZZ` double circled, and the edges of each ghost loop in the color of the ghost.
+    fn to_dot(&self, ghosts: &[GhostCycle]) -> String {
+        let mut edge_colors: HashMap<(&str, &str), Vec<&str>> = HashMap::new();
+        for (ghost, color) in ghosts.iter().zip(GHOST_COLORS.iter().cycle()) {
+            for (from, to) in ghost.loop_edges().unique() {
+                edge_colors.entry((from, to)).or_default().push(color);
+            }
+        }
+
+        let mut dot = String::from("digraph network {\n    node [shape=circle, style=filled, fillcolor=white];\n");
+        for node in self.node_map.values().sorted_by(|a, b| a.key.cmp(&b.key)) {
+            let mut attributes = vec![];
+            if node.is_start_node() {
+                attributes.push("fillcolor=palegreen".to_string());
+            } else if node.is_end_node() {
+                attributes.push("fillcolor=salmon".to_string());
+            }
+            if node.key == START_NODE || node.key == END_NODE {
+                attributes.push("shape=doublecircle".to_string());
+            }
+            dot.push_str(&format!("    \"{}\" [{}];\n", node.key, attributes.join(", ")));
+        }
 
-        if current_nodes.iter().any(|node| node.is_end_node()) {
-            lcm = num::integer::lcm(lcm, steps_count);
+        for node in self.node_map.values().sorted_by(|a, b| a.key.cmp(&b.key)) {
+            let left = node.get_next_node(&Instruction::Left);
+            let right = node.get_next_node(&Instruction::Right);
+            let edges = match left == right {
+                true => vec![("L/R", left)],
+                false => vec![("L", left), ("R", right)],
+            };
+            for (label, next) in edges {
+                let mut attributes = vec![format!("label=\"{}\"", label)];
+                if let Some(colors) = edge_colors.get(&(node.key.as_str(), next.as_str())) {
+                    attributes.push(format!("color=\"{}\", penwidth=2", colors.join(":")));
+                }
+                dot.push_str(&format!("    \"{}\" -> \"{}\" [{}];\n", node.key, next, attributes.join(", ")));
+            }
         }
 
-        current_nodes = current_nodes
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Where a ghost stands over time: after `prefix` steps, it loops every `length` steps.
+#[derive(Debug)]
+struct GhostCycle {
+    start: String,
+    prefix: usize,
+    length: usize,
+    /// Steps before the loop at which the ghost is on an end node.
+    transient_hits: Vec<usize>,
+    /// Steps of the first turn of the loop at which the ghost is on an end node, each repeating
+    /// every `length` steps.
+    cycle_hits: Vec<usize>,
+    /// Nodes the ghost is on, from its start to the end of the first turn of the loop.
+    path: Vec<String>,
+}
+
+impl GhostCycle {
+    fn from_path(path: Vec<&Node>, prefix: usize, is_end: impl Fn(&Node) -> bool) -> GhostCycle {
+        let (transient_hits, cycle_hits) = path
             .iter()
-            .filter(|node| !node.is_end_node())
-            .map(|&node| node)
-            .collect::<Vec<&Node>>();
+            .positions(|node| is_end(node))
+            .partition(|&step| step < prefix);
 
-        if current_nodes.len() == 0 {
-            break;
+        GhostCycle {
+            start: path[0].key.clone(),
+            prefix,
+            length: path.len() - prefix,
+            transient_hits,
+            cycle_hits,
+            path: path.iter().map(|node| node.key.clone()).collect(),
         }
     }
 
-    println!("LCM: {:?}", lcm);
+    fn first_hit(&self) -> Option<usize> {
+        self.transient_hits.first().or(self.cycle_hits.first()).copied()
+    }
+
+    /// Edges walked in the loop, which closes back on the node at `prefix`.
+    fn loop_edges(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        let cycle = &self.path[self.prefix..];
+        cycle.iter().zip(cycle.iter().cycle().skip(1)).map(|(from, to)| (from.as_str(), to.as_str()))
+    }
+
+    fn is_on_end_node(&self, step: usize) -> bool {
+        if step < self.prefix {
+            self.transient_hits.contains(&step)
+        } else {
+            let step = self.prefix + (step - self.prefix) % self.length;
+            self.cycle_hits.contains(&step)
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{}: loops every {} steps after {}, on end nodes at steps {:?} then {:?} (+ k * {})",
+            self.start, self.length, self.prefix, self.transient_hits, self.cycle_hits, self.length
+        )
+    }
+}
+
+/// First step at which every ghost is on an end node. Before every ghost is looping, the only
+/// candidates are the hits of the ghosts which are not yet; after that, the hits of all the
+/// loops are combined into congruences modulo the lcm of their lengths.
+fn find_sync(ghosts: &[GhostCycle]) -> Option<u64> {
+    let looping_from = ghosts.iter().map(|ghost| ghost.prefix).max()?;
+
+    let transient_sync = ghosts
+        .iter()
+        .flat_map(|ghost| ghost.transient_hits.iter().copied())
+        .sorted()
+        .find(|&step| ghosts.iter().all(|ghost| ghost.is_on_end_node(step)));
+    if let Some(step) = transient_sync {
+        return Some(step as u64);
+    }
+
+    let mut congruences = vec![(0, 1)];
+    for ghost in ghosts.iter() {
+        let length = ghost.length as i128;
+        congruences = congruences
+            .iter()
+            .cartesian_product(ghost.cycle_hits.iter())
+            .filter_map(|(&congruence, &hit)| combine_congruences(congruence, (hit as i128 % length, length)))
+            .unique()
+            .collect();
+    }
+
+    let looping_from = looping_from as i128;
+    congruences
+        .iter()
+        .map(|&(residue, modulus)| looping_from + (residue - looping_from).rem_euclid(modulus))
+        .min()
+        .map(|step| step as u64)
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -1,63 +1,45 @@
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values, has_flag, NewtonPolynomial};
+use itertools::Itertools;
+use num::BigInt;
 
 fn main() {
     let input_lines = get_aoc_input_lines().expect("Error getting input");
 
-    let sum_extrapolated_values = input_lines
-        .map(|line| parse_line(&line.expect("Error reading line")))
-        .map(|history| extrapolate_next(&history))
-        .map(|history| extrapolate_previous(&history))
-        .map(|extrapolated| (extrapolated.first().unwrap().clone(), extrapolated.last().unwrap().clone()))
-        .fold((0, 0), |(s_i, f_i), (s, f)| (s_i + s, f_i + f));
-    println!("Sum of extrapolated values (prev, next): {:?}", sum_extrapolated_values);
-}
+    let histories = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_line(&line))
+        .collect_vec();
+    let models = histories.iter().map(|history| NewtonPolynomial::from_samples(history)).collect_vec();
 
-fn parse_line(line: &str) -> Vec<i64> {
-    line.split_whitespace()
-        .map(|s| s.parse::<i64>().expect("Error parsing number"))
-        .collect()
-}
-
-fn extrapolate_next(history: &Vec<i64>) -> Vec<i64> {
-    let mut history = history.clone();
-    let differentials = history.differentiate();
-    
-    let last_value = history.last().unwrap();
-    let end_reached = differentials.iter().all(|&d| d == 0);
-
-    if end_reached {
-        history.push(*last_value);
-    } else {
-        history.push(*last_value + extrapolate_next(&differentials).last().unwrap());
+    for i in models.iter().positions(|model| !model.reaches_zero_row()) {
+        eprintln!("Warning: line {} never reaches a row of zero differences, its extrapolations are guesses", i + 1);
     }
 
-    history
-}
-
-fn extrapolate_previous(history: &Vec<i64>) -> Vec<i64> {
-    let mut history = history.clone();
-    let differentials = history.differentiate();
-    
-    let first_value = history.first().unwrap();
-    let end_reached = differentials.iter().all(|&d| d == 0);
+    if has_flag("--degrees") {
+        for (i, model) in models.iter().enumerate() {
+            println!("Line {}: degree {}", i + 1, model.degree());
+        }
+    }
 
-    if end_reached {
-        history.insert(0, *first_value);
-    } else {
-        history.insert(0, *first_value - extrapolate_previous(&differentials).first().unwrap());
+    // `--at INDEX`: sum of the values at an index of every history, 0 being the first value
+    if let Some(values) = get_flag_values("--at", 1) {
+        let index = values[0].parse::<i64>().expect("Error parsing --at, expected an index");
+        let sum: BigInt = models.iter().map(|model| model.value_at(index)).sum();
+        println!("Sum of values at index {}: {}", index, sum);
     }
 
-    history
+    let previous: BigInt = models.iter().map(|model| model.value_at(-1)).sum();
+    let next: BigInt = histories
+        .iter()
+        .zip(models.iter())
+        .map(|(history, model)| model.value_at(history.len() as i64))
+        .sum();
+    println!("Sum of extrapolated values (prev, next): ({}, {})", previous, next);
 }
 
-trait Differentiable {
-    fn differentiate(&self) -> Self;
+fn parse_line(line: &str) -> Vec<i64> {
+    line.split_whitespace()
+        .map(|s| s.parse::<i64>().expect("Error parsing number"))
+        .collect()
 }
-
-impl Differentiable for Vec<i64> {
-    fn differentiate(&self) -> Self {
-        self.windows(2)
-            .map(|w| w[1] - w[0])
-            .collect()
-    }
-}
\ No newline at end of file
@@ -12,7 +12,7 @@ fn main() {
         .collect();
 
     let start = (0..grid.len())
-        .find_map(|i| (0..grid[i].len()).find_map(|j| (grid[i][j].start == true).then_some((i, j))))
+        .find_map(|i| (0..grid[i].len()).find_map(|j| (grid[i][j].start).then_some((i, j))))
         .unwrap();
 
     grid[start.0][start.1].north = start.0 > 0 && grid[start.0 - 1][start.1].south;
@@ -47,10 +47,10 @@ fn main() {
     println!("{}", distance);
 
     // Part 2
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
+    for (i, row) in grid.iter_mut().enumerate() {
+        for (j, tile) in row.iter_mut().enumerate() {
             if !visited.contains(&(i, j)) {
-                grid[i][j] = Default::default();
+                *tile = Default::default();
             }
         }
     }
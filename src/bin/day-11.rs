@@ -1,6 +1,6 @@
-use std::{io::{BufReader, Lines}, fs::File, collections::{HashSet, HashMap}};
+use std::collections::{HashSet, HashMap};
 
-use advent_of_code_2023::utils::get_aoc_input_lines;
+use advent_of_code_2023::utils::{get_aoc_input_lines, AocInputLines};
 
 fn main() {
     let input_lines = get_aoc_input_lines().expect("Error getting input");
@@ -49,7 +49,7 @@ struct SpaceGrid {
 }
 
 impl SpaceGrid {
-    fn from_input_lines(input_lines: Lines<BufReader<File>>) -> SpaceGrid {
+    fn from_input_lines(input_lines: AocInputLines) -> SpaceGrid {
         let mut grid = Vec::new();
         for line in input_lines {
             let line = line.expect("Error reading line");
@@ -60,7 +60,7 @@ impl ConditionRecord {
         let mut split_string = s.split_whitespace();
 
         let springs = split_string.next().expect("Error reading springs");
-        let springs = springs.chars().map(|c| Spring::from_char(c)).collect();
+        let springs = springs.chars().map(Spring::from_char).collect();
 
         let damaged_sequence = split_string.next().expect("Error reading damaged sequence");
         let damaged_sequence = damaged_sequence
@@ -89,12 +89,12 @@ impl ConditionRecord {
         let springs = self.springs.clone();
 
         ConditionRecord {
-            springs: repeat_n(springs, n).enumerate().map(|(i, mut s)| {
+            springs: repeat_n(springs, n).enumerate().flat_map(|(i, mut s)| {
                 if (i + 1) != n {
                     s.push(Spring::Unknown);
                 }
                 s
-            }).flatten().collect_vec(),
+            }).collect_vec(),
             damaged_sequence: repeat_n(self.damaged_sequence.clone(), n)
                 .flatten()
                 .collect_vec(),
@@ -111,7 +111,7 @@ impl ConditionRecord {
     convert = r#"{ format!("{:?}-{:?}-{}", springs, damaged_sequence, is_initial_call) }"#
 )]
 fn count_permutations(springs: Vec<Spring>, damaged_sequence: Vec<i64>, is_initial_call: bool) -> i64 {
-    if damaged_sequence.len() == 0 {
+    if damaged_sequence.is_empty() {
         return if springs.contains(&Spring::Damaged) {0} else {1};
     }
 
@@ -130,7 +130,7 @@ fn count_permutations(springs: Vec<Spring>, damaged_sequence: Vec<i64>, is_initi
     permutations_count
 }
 
-fn valid_permutation(springs: &Vec<Spring>, start: i64, end: i64, is_initial_call: bool) -> bool {
+fn valid_permutation(springs: &[Spring], start: i64, end: i64, is_initial_call: bool) -> bool {
     // Not out of bounds
     if start < 0 || end >= springs.len() as i64 {
         return false;
@@ -26,7 +26,7 @@ fn main() {
     println!("Reflection summary: {}", reflection_summary);
 }
 
-fn process_pattern(pattern: &Vec<Vec<char>>) -> (Option<usize>, Option<usize>) {
+fn process_pattern(pattern: &[Vec<char>]) -> (Option<usize>, Option<usize>) {
     let reflection_row = find_reflection_row(pattern, None);
     let reflection_column = find_reflection_column(pattern, None);
     let ignored_rows = reflection_row.into_iter().collect::<HashSet<usize>>();
@@ -34,7 +34,7 @@ fn process_pattern(pattern: &Vec<Vec<char>>) -> (Option<usize>, Option<usize>) {
 
     for i in 0..pattern.len() {
         for j in 0..pattern[i].len() {
-            let mut pattern = pattern.clone();
+            let mut pattern = pattern.to_vec();
             pattern[i][j] = match pattern[i][j] {
                 '.' => '#',
                 '#' => '.',
@@ -70,7 +70,7 @@ fn update_reflection_summary(
     }
 }
 
-fn find_reflection_row(pattern: &Vec<Vec<char>>, ignores: Option<&HashSet::<usize>>) -> Option<usize> {
+fn find_reflection_row(pattern: &[Vec<char>], ignores: Option<&HashSet::<usize>>) -> Option<usize> {
     pattern
         .iter()
         .enumerate()
@@ -84,7 +84,7 @@ fn find_reflection_row(pattern: &Vec<Vec<char>>, ignores: Option<&HashSet::<usiz
                 }
             },
         )
-        .filter(|initial_guess| {
+        .find(|initial_guess| {
             let initial_guess = *initial_guess;
             if ignores.map(|ignores| ignores.contains(&initial_guess)).unwrap_or(false) {
                 return false;
@@ -101,10 +101,9 @@ fn find_reflection_row(pattern: &Vec<Vec<char>>, ignores: Option<&HashSet::<usiz
 
             true
         })
-        .next()
 }
 
-fn find_reflection_column(pattern: &Vec<Vec<char>>, ignores: Option<&HashSet<usize>>) -> Option<usize> {
+fn find_reflection_column(pattern: &[Vec<char>], ignores: Option<&HashSet<usize>>) -> Option<usize> {
     let width = pattern[0].len();
     let pattern = (0..width)
         .map(|i| pattern.iter().map(|row| row[i]).collect_vec())
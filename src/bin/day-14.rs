@@ -14,7 +14,7 @@ fn main() {
     println!("Total load: {}", total_load);
 }
 
-fn compute_grid_load(grid: &Vec<Vec<char>>) -> usize {
+fn compute_grid_load(grid: &[Vec<char>]) -> usize {
     let row_count = grid.len();
     grid.iter()
         .enumerate()
@@ -22,8 +22,8 @@ fn compute_grid_load(grid: &Vec<Vec<char>>) -> usize {
         .sum()
 }
 
-fn tilt_grid_n_cycles(grid: &Vec<Vec<char>>, n: usize) -> Vec<Vec<char>> {
-    let mut tilted_grid = grid.clone();
+fn tilt_grid_n_cycles(grid: &[Vec<char>], n: usize) -> Vec<Vec<char>> {
+    let mut tilted_grid = grid.to_vec();
     let style = indicatif::ProgressStyle::with_template(
         "[{elapsed_precise}/{eta_precise}] {bar:40.cyan/blue} {human_pos:>7}/{human_len:7} ({percent} %) @{per_sec} {msg}"
     ).unwrap();
@@ -36,7 +36,7 @@ fn tilt_grid_n_cycles(grid: &Vec<Vec<char>>, n: usize) -> Vec<Vec<char>> {
     tilted_grid
 }
 
-fn tilt_grid_up(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+fn tilt_grid_up(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     let grid = transpose(grid);
     let grid = grid
         .iter()
@@ -45,7 +45,7 @@ fn tilt_grid_up(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
     transpose(&grid)
 }
 
-fn tilt_grid_down(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+fn tilt_grid_down(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     let grid = transpose(grid);
     let grid = grid
         .iter()
@@ -60,11 +60,11 @@ fn tilt_grid_down(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
     transpose(&grid)
 }
 
-fn tilt_grid_left(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+fn tilt_grid_left(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     grid.iter().map(|row| tilt_row_left(row)).collect_vec()
 }
 
-fn tilt_grid_right(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+fn tilt_grid_right(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     grid.iter()
         .map(|row| {
             tilt_row_left(&row.iter().rev().cloned().collect_vec())
@@ -76,14 +76,14 @@ fn tilt_grid_right(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
         .collect_vec()
 }
 
-fn transpose(grid: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let width = grid.get(0).map(|row| row.len()).unwrap_or(0);
+fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    let width = grid.first().map(|row| row.len()).unwrap_or(0);
     (0..width)
         .map(|i| grid.iter().map(|row| row[i]).collect_vec())
         .collect_vec()
 }
 
-fn tilt_row_left(column: &Vec<char>) -> Vec<char> {
+fn tilt_row_left(column: &[char]) -> Vec<char> {
     let mut tilted_column = Vec::new();
     let mut insertion_index = 0;
     for (i, cell) in column.iter().enumerate() {
@@ -106,11 +106,11 @@ fn tilt_row_left(column: &Vec<char>) -> Vec<char> {
 }
 
 #[allow(dead_code)]
-fn format_row(row: &Vec<char>) -> String {
+fn format_row(row: &[char]) -> String {
     row.iter().collect()
 }
 
-fn format_grid(grid: &Vec<Vec<char>>) -> String {
+fn format_grid(grid: &[Vec<char>]) -> String {
     let mut representation = String::new();
     for row in grid {
         for cell in row {
@@ -122,6 +122,6 @@ fn format_grid(grid: &Vec<Vec<char>>) -> String {
 }
 
 #[allow(dead_code)]
-fn print_grid(grid: &Vec<Vec<char>>) {
+fn print_grid(grid: &[Vec<char>]) {
     print!("{}", format_grid(grid));
 }
@@ -1,4 +1,3 @@
-use std::usize;
 
 use advent_of_code_2023::utils::get_aoc_input_lines;
 
@@ -15,7 +14,7 @@ fn main() {
 
     // Part 2
     let mut boxes: Vec<Vec<(String, usize)>> = vec![vec![]; 256];
-    let operations = input_line.split(",").map(|s| Operation::from_str(s));
+    let operations = input_line.split(",").map(Operation::from_str);
     for operation in operations {
         match operation {
             Operation::Remove(op) => {
@@ -40,7 +39,7 @@ fn main() {
     println!("Focusing power: {}", focusing_power);
 }
 
-fn compute_focusing_power(boxes: &Vec<Vec<(String, usize)>>) -> usize {
+fn compute_focusing_power(boxes: &[Vec<(String, usize)>]) -> usize {
     boxes.iter().enumerate().map(|(box_idx, the_box)| {
         the_box
             .iter()
@@ -57,7 +56,7 @@ fn hash(input: &str) -> usize {
 }
 
 #[allow(dead_code)]
-fn print_boxes(boxes: &Vec<Vec<(String, usize)>>) {
+fn print_boxes(boxes: &[Vec<(String, usize)>]) {
     for (i, box_) in boxes.iter().enumerate() {
         if !box_.is_empty() {
             println!("{}: {:?}", i, box_);
@@ -47,8 +47,7 @@ fn main() {
                 for beam in beams.iter_mut() {
                     let object = grid
                         .get(beam.head.row as usize)
-                        .map(|row| row.get(beam.head.column as usize))
-                        .flatten();
+                        .and_then(|row| row.get(beam.head.column as usize));
                     if object.is_none() {
                         continue;
                     }
@@ -156,9 +155,7 @@ impl Coord {
     }
 
     fn swap(&mut self) {
-        let temp = self.row;
-        self.row = self.column;
-        self.column = temp;
+        std::mem::swap(&mut self.row, &mut self.column);
     }
 
     fn multiply(&mut self, factor: i32) {
@@ -166,7 +163,7 @@ impl Coord {
         self.column *= factor;
     }
 
-    fn is_out_of_bounds(&self, grid: &Vec<Vec<Object>>) -> bool {
+    fn is_out_of_bounds(&self, grid: &[Vec<Object>]) -> bool {
         self.row < 0
             || self.column < 0
             || self.row >= grid.len() as i32
@@ -231,7 +228,7 @@ impl Beam {
 }
 
 #[allow(dead_code)]
-fn print_grid(grid: &Vec<Vec<Object>>, energized_tiles: &Vec<&Coord>) {
+fn print_grid(grid: &[Vec<Object>], energized_tiles: &[&Coord]) {
     for (row_index, row) in grid.iter().enumerate() {
         for (column_index, object) in row.iter().enumerate() {
             let coord = Coord::new(row_index as i32, column_index as i32);
@@ -126,12 +126,12 @@ impl Pos {
         }
     }
 
-    fn into_successor(self, grid: &Vec<Vec<u32>>) -> (Self, u32) {
+    fn into_successor(self, grid: &[Vec<u32>]) -> (Self, u32) {
         let cost = grid[self.row as usize][self.column as usize];
         (self, cost)
     }
 
-    fn is_out_of_bounds(&self, grid: &Vec<Vec<u32>>) -> bool {
+    fn is_out_of_bounds(&self, grid: &[Vec<u32>]) -> bool {
         self.row < 0
             || self.row >= grid.len() as i32
             || self.column < 0
@@ -142,7 +142,7 @@ impl Pos {
         self.consecutive_direction_count > MAX_CONSECUCTIVE_DIRECTION
     }
 
-    fn succesors(&self, grid: &Vec<Vec<u32>>) -> Vec<(Self, u32)> {
+    fn succesors(&self, grid: &[Vec<u32>]) -> Vec<(Self, u32)> {
         if self.direction == Direction::Start {
             return vec![
                 self.go_right().into_successor(grid),
@@ -167,7 +167,7 @@ impl Pos {
         ]
         .iter()
         .filter(|direction| **direction != self.direction.opposite())
-        .map(|direction| {
+        .filter_map(|direction| {
             let new_pos = match direction {
                 Direction::Up => self.go_up(),
                 Direction::Down => self.go_down(),
@@ -182,14 +182,12 @@ impl Pos {
 
             Some(new_pos.into_successor(grid))
         })
-        .filter(|succ| succ.is_some())
-        .map(|succ| succ.unwrap())
         .collect_vec()
     }
 }
 
 #[allow(dead_code)]
-fn print_grid<T>(grid: &Vec<Vec<T>>)
+fn print_grid<T>(grid: &[Vec<T>])
 where
     T: std::fmt::Display,
 {
@@ -44,7 +44,7 @@ fn main() {
     println!("Area - Part 2: {}", compute_area(&points_part_2));
 }
 
-fn compute_area(points: &Vec<(i64, i64)>) -> i64 {
+fn compute_area(points: &[(i64, i64)]) -> i64 {
     // Gauss's shoelace formula
     let mut area = 0;
     let mut perimeter = 0;
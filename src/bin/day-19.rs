@@ -74,7 +74,7 @@ impl Workflow {
         let id = parts.next().expect("Error parsing workflow id").to_string();
         
         let rules = parts.next().expect("Error parsing workflow rules");
-        let rules = rules.split(",").map(|rule| Rule::from_str(rule)).collect();
+        let rules = rules.split(",").map(Rule::from_str).collect();
 
         Self {
             id,
@@ -256,7 +256,7 @@ impl PartRangeRatings {
             range = opposite_range;
         }
 
-        return counter;
+        counter
     }
 
     fn get_total_combinations(&self) -> i64 {
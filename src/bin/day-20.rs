@@ -0,0 +1,249 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt, process,
+};
+
+use advent_of_code_2023::utils::get_aoc_input_lines;
+use itertools::Itertools;
+
+const BUTTON_PRESSES: usize = 1000;
+const MAX_BUTTON_PRESSES_TO_RX: u64 = 1_000_000;
+
+fn main() {
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let network = Network::from_lines(input_lines.map(|line| line.expect("Error reading line")));
+
+    // Part 1
+    let mut part_1_network = network.clone();
+    let mut low_pulses = 0;
+    let mut high_pulses = 0;
+    for _ in 0..BUTTON_PRESSES {
+        part_1_network.push_button(|_, _, pulse| match pulse {
+            Pulse::Low => low_pulses += 1,
+            Pulse::High => high_pulses += 1,
+        });
+    }
+    println!("Pulses product - Part 1: {}", low_pulses * high_pulses);
+
+    // Part 2, the examples having no `rx` module
+    if network.get_inputs("rx").is_empty() {
+        return;
+    }
+    match network.clone().count_presses_until_rx_low() {
+        Ok(presses) => println!("Button presses to rx - Part 2: {}", presses),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum RxError {
+    /// `rx` is not fed by exactly one conjunction, but by these modules.
+    NoSingleConjunctionFeeder(Vec<String>),
+    /// These inputs of the conjunction feeding `rx` sent less than two high pulses within the press limit.
+    SilentFeederInputs { feeder: String, inputs: Vec<String> },
+    /// This input did not send its second high pulse twice as many presses in as its first one,
+    /// so its pulses do not repeat with the period of the first one.
+    IrregularCycle { input: String, first: u64, second: u64 },
+}
+
+impl fmt::Display for RxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RxError::NoSingleConjunctionFeeder(feeders) => {
+                write!(f, "rx is not fed by a single conjunction but by [{}]", feeders.join(", "))
+            }
+            RxError::SilentFeederInputs { feeder, inputs } => write!(
+                f,
+                "{} did not send two high pulses to {} within {} button presses",
+                inputs.join(", "),
+                feeder,
+                MAX_BUTTON_PRESSES_TO_RX
+            ),
+            RxError::IrregularCycle { input, first, second } => write!(
+                f,
+                "{} sent high pulses after {} and {} button presses, which is not a cycle starting at 0",
+                input, first, second
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop { on: bool },
+    Conjunction { memory: HashMap<String, Pulse> },
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    name: String,
+    kind: ModuleKind,
+    destinations: Vec<String>,
+}
+
+impl Module {
+    fn from_str(input_line: &str) -> Self {
+        let (name, destinations) = input_line
+            .split(" -> ")
+            .collect_tuple()
+            .expect("Error parsing module");
+        let destinations = destinations.split(',').map(|destination| destination.trim().to_string()).collect();
+
+        let (name, kind) = if let Some(name) = name.strip_prefix('%') {
+            (name, ModuleKind::FlipFlop { on: false })
+        } else if let Some(name) = name.strip_prefix('&') {
+            (name, ModuleKind::Conjunction { memory: HashMap::new() })
+        } else if name == "broadcaster" {
+            (name, ModuleKind::Broadcaster)
+        } else {
+            panic!("Error parsing module type for line {}", input_line);
+        };
+
+        Self {
+            name: name.to_string(),
+            kind,
+            destinations,
+        }
+    }
+
+    /// Returns the pulse sent to every destination, if any.
+    fn receive(&mut self, from: &str, pulse: Pulse) -> Option<Pulse> {
+        match &mut self.kind {
+            ModuleKind::Broadcaster => Some(pulse),
+            ModuleKind::FlipFlop { on } => {
+                if pulse == Pulse::High {
+                    return None;
+                }
+
+                *on = !*on;
+                Some(if *on { Pulse::High } else { Pulse::Low })
+            }
+            ModuleKind::Conjunction { memory } => {
+                memory.insert(from.to_string(), pulse);
+                if memory.values().all(|&remembered| remembered == Pulse::High) {
+                    Some(Pulse::Low)
+                } else {
+                    Some(Pulse::High)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Network {
+    modules: HashMap<String, Module>,
+}
+
+impl Network {
+    fn from_lines(input_lines: impl Iterator<Item = String>) -> Self {
+        let mut modules: HashMap<String, Module> = input_lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Module::from_str(&line))
+            .map(|module| (module.name.clone(), module))
+            .collect();
+
+        // Conjunctions start remembering a low pulse for each of their inputs
+        let connections = modules
+            .values()
+            .flat_map(|module| module.destinations.iter().map(|destination| (module.name.clone(), destination.clone())))
+            .collect_vec();
+        for (from, to) in connections {
+            if let Some(Module { kind: ModuleKind::Conjunction { memory }, .. }) = modules.get_mut(&to) {
+                memory.insert(from, Pulse::Low);
+            }
+        }
+
+        Self { modules }
+    }
+
+    fn get_inputs(&self, name: &str) -> Vec<String> {
+        self.modules
+            .values()
+            .filter(|module| module.destinations.iter().any(|destination| destination == name))
+            .map(|module| module.name.clone())
+            .collect()
+    }
+
+    /// Sends a low pulse to the broadcaster and processes pulses in the order they were sent,
+    /// calling `on_pulse` with `(from, to, pulse)` for each of them.
+    fn push_button(&mut self, mut on_pulse: impl FnMut(&str, &str, Pulse)) {
+        let mut queue = VecDeque::from([("button".to_string(), "broadcaster".to_string(), Pulse::Low)]);
+
+        while let Some((from, to, pulse)) = queue.pop_front() {
+            on_pulse(&from, &to, pulse);
+
+            // Untyped modules such as `output` or `rx` only receive pulses
+            let Some(module) = self.modules.get_mut(&to) else {
+                continue;
+            };
+
+            if let Some(next_pulse) = module.receive(&from, pulse) {
+                for destination in module.destinations.iter() {
+                    queue.push_back((to.clone(), destination.clone(), next_pulse));
+                }
+            }
+        }
+    }
+
+    /// `rx` is fed by a single conjunction, which only sends a low pulse once all of its inputs
+    /// sent a high pulse during the same button press. Each input does so periodically, so the
+    /// answer is the LCM of the number of presses it takes each of them to send its first one.
+    /// That only holds if their second one comes twice as many presses in, which is checked.
+    fn count_presses_until_rx_low(&mut self) -> Result<u64, RxError> {
+        let feeders = self.get_inputs("rx");
+        let feeder = match feeders.as_slice() {
+            [feeder] if matches!(self.modules[feeder].kind, ModuleKind::Conjunction { .. }) => feeder.clone(),
+            _ => return Err(RxError::NoSingleConjunctionFeeder(feeders.into_iter().sorted().collect())),
+        };
+
+        let feeder_inputs = self.get_inputs(&feeder);
+        // Presses after which each input sent its first two high pulses
+        let mut high_presses: HashMap<String, Vec<u64>> = HashMap::new();
+        for presses in 1..=MAX_BUTTON_PRESSES_TO_RX {
+            self.push_button(|from, to, pulse| {
+                if to == feeder && pulse == Pulse::High {
+                    let input_presses = high_presses.entry(from.to_string()).or_default();
+                    if input_presses.len() < 2 && input_presses.last() != Some(&presses) {
+                        input_presses.push(presses);
+                    }
+                }
+            });
+
+            if feeder_inputs.iter().all(|input| high_presses.get(input).is_some_and(|presses| presses.len() == 2)) {
+                break;
+            }
+        }
+
+        let silent_inputs = feeder_inputs
+            .iter()
+            .filter(|input| high_presses.get(*input).is_none_or(|presses| presses.len() < 2))
+            .cloned()
+            .sorted()
+            .collect_vec();
+        if !silent_inputs.is_empty() {
+            return Err(RxError::SilentFeederInputs { feeder, inputs: silent_inputs });
+        }
+
+        let mut cycle_length = 1;
+        for input in feeder_inputs.into_iter().sorted() {
+            let (first, second) = (high_presses[&input][0], high_presses[&input][1]);
+            if second != 2 * first {
+                return Err(RxError::IrregularCycle { input, first, second });
+            }
+            cycle_length = num::integer::lcm(cycle_length, first);
+        }
+
+        Ok(cycle_length)
+    }
+}
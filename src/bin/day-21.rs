@@ -0,0 +1,145 @@
+use std::{collections::HashSet, process};
+
+use advent_of_code_2023::utils::{get_aoc_input_lines, NewtonPolynomial};
+use itertools::Itertools;
+
+const PART_1_STEPS: usize = 64;
+const PART_2_STEPS: usize = 26501365;
+// Number of consecutive zero third differences needed to trust the quadratic growth
+const STABLE_SAMPLES: usize = 3;
+// Samples stop doubling past this many, each of them walking one more tile in every direction
+const MAX_SAMPLES: usize = 384;
+
+fn main() {
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let garden = Garden::from_lines(input_lines.map(|line| line.expect("Error reading line")));
+
+    let reachable_plots = garden.count_reachable_plots(PART_1_STEPS, false);
+    println!("Reachable plots - Part 1: {}", reachable_plots);
+
+    match garden.extrapolate_reachable_plots(PART_2_STEPS) {
+        Ok(reachable_plots) => println!("Reachable plots on the infinite map - Part 2: {}", reachable_plots),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Garden {
+    rocks: Vec<Vec<bool>>,
+    start: (i64, i64),
+}
+
+impl Garden {
+    fn from_lines(input_lines: impl Iterator<Item = String>) -> Self {
+        let mut start = None;
+        let rocks = input_lines
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        if c == 'S' {
+                            start = Some((row as i64, column as i64));
+                        }
+                        c == '#'
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        Self {
+            rocks,
+            start: start.expect("Error finding start position"),
+        }
+    }
+
+    fn height(&self) -> i64 {
+        self.rocks.len() as i64
+    }
+
+    fn width(&self) -> i64 {
+        self.rocks[0].len() as i64
+    }
+
+    fn is_rock(&self, (row, column): (i64, i64), infinite: bool) -> bool {
+        if !infinite && (row < 0 || row >= self.height() || column < 0 || column >= self.width()) {
+            return true;
+        }
+
+        self.rocks[row.rem_euclid(self.height()) as usize][column.rem_euclid(self.width()) as usize]
+    }
+
+    /// BFS from the start, returning how many plots are first reached after each number of steps.
+    fn count_plots_per_distance(&self, max_steps: usize, infinite: bool) -> Vec<usize> {
+        let mut visited = HashSet::from([self.start]);
+        let mut frontier = vec![self.start];
+        let mut plots_per_distance = vec![1];
+
+        for _ in 0..max_steps {
+            frontier = frontier
+                .iter()
+                .flat_map(|&(row, column)| [(row - 1, column), (row + 1, column), (row, column - 1), (row, column + 1)])
+                .filter(|&position| !self.is_rock(position, infinite) && visited.insert(position))
+                .collect_vec();
+            plots_per_distance.push(frontier.len());
+        }
+
+        plots_per_distance
+    }
+
+    /// A plot can be ended on in exactly `steps` steps if it can be reached in fewer steps
+    /// of the same parity, by walking back and forth.
+    fn count_reachable_plots(&self, steps: usize, infinite: bool) -> usize {
+        self.count_plots_per_distance(steps, infinite)
+            .iter()
+            .skip(steps % 2)
+            .step_by(2)
+            .sum()
+    }
+
+    /// On the infinite map, the number of reachable plots after `steps % width + k * width` steps
+    /// grows quadratically in `k` once the walk spreads over whole tiles. Samples are taken until
+    /// their third differences settle to zero, then extrapolated to the requested number of steps.
+    /// Fails if they have not settled after `MAX_SAMPLES` samples.
+    fn extrapolate_reachable_plots(&self, steps: usize) -> Result<i64, String> {
+        let width = self.width() as usize;
+        let remainder = steps % width;
+        let target_sample = steps / width;
+
+        let mut sample_count = STABLE_SAMPLES + 3;
+        let samples = loop {
+            let plots_per_distance = self.count_plots_per_distance(remainder + (sample_count - 1) * width, true);
+            let samples = (0..sample_count)
+                .map(|k| {
+                    let sample_steps = remainder + k * width;
+                    plots_per_distance[..=sample_steps].iter().skip(sample_steps % 2).step_by(2).sum::<usize>() as i64
+                })
+                .collect_vec();
+
+            // The last third differences are all zero when the last samples fit a quadratic
+            let last_samples = NewtonPolynomial::from_samples(&samples[samples.len() - STABLE_SAMPLES - 3..]);
+            if sample_count > target_sample || (last_samples.reaches_zero_row() && last_samples.degree() <= 2) {
+                break samples;
+            }
+            if sample_count >= MAX_SAMPLES {
+                return Err(format!("Reachable plots did not grow quadratically within {} samples", sample_count));
+            }
+
+            sample_count = (sample_count * 2).min(MAX_SAMPLES);
+        };
+
+        if target_sample < samples.len() {
+            return Ok(samples[target_sample]);
+        }
+
+        // The quadratic through the last three samples, indexed from the first of them
+        let first_index = samples.len() - 3;
+        let quadratic = NewtonPolynomial::from_samples(&samples[first_index..]);
+        let reachable_plots = quadratic.value_at((target_sample - first_index) as i64);
+        i64::try_from(&reachable_plots).map_err(|_| format!("{} reachable plots do not fit in i64", reachable_plots))
+    }
+}
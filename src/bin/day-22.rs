@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use advent_of_code_2023::utils::get_aoc_input_lines;
+use itertools::Itertools;
+
+// Index of the virtual brick standing for the ground in the support graph
+const GROUND: usize = 0;
+
+fn main() {
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let bricks = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Brick::from_str(&line))
+        .sorted_by_key(|brick| brick.start.2)
+        .collect_vec();
+
+    let stack = Stack::settle(&bricks);
+
+    println!("Safe bricks to disintegrate - Part 1: {}", stack.count_safe_bricks());
+    println!("Sum of chain reaction falls - Part 2: {}", stack.count_chain_reaction_falls());
+}
+
+#[derive(Debug, Clone)]
+struct Brick {
+    start: (usize, usize, usize),
+    end: (usize, usize, usize),
+}
+
+impl Brick {
+    fn from_str(input_line: &str) -> Self {
+        let (start, end) = input_line
+            .split('~')
+            .map(|coordinates| {
+                coordinates
+                    .split(',')
+                    .map(|coordinate| coordinate.trim().parse::<usize>().expect("Error parsing coordinate"))
+                    .collect_tuple::<(usize, usize, usize)>()
+                    .expect("Error parsing brick end")
+            })
+            .collect_tuple()
+            .expect("Error parsing brick");
+
+        // Make sure the start is the lowest corner
+        Self {
+            start: (start.0.min(end.0), start.1.min(end.1), start.2.min(end.2)),
+            end: (start.0.max(end.0), start.1.max(end.1), start.2.max(end.2)),
+        }
+    }
+
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start.0..=self.end.0).cartesian_product(self.start.1..=self.end.1)
+    }
+
+    fn height(&self) -> usize {
+        self.end.2 - self.start.2 + 1
+    }
+}
+
+/// Support graph of the settled bricks. Bricks are numbered from 1 in settling order,
+/// which is a topological order as a brick always settles after the ones holding it.
+#[derive(Debug)]
+struct Stack {
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Drops the bricks, sorted by altitude, onto a height map keeping the top brick of each column.
+    fn settle(bricks: &[Brick]) -> Self {
+        let mut height_map: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut supports = vec![vec![]; bricks.len() + 1];
+        let mut supported_by = vec![vec![]; bricks.len() + 1];
+
+        for (i, brick) in bricks.iter().enumerate() {
+            let id = i + 1;
+            let tops = brick
+                .footprint()
+                .map(|cell| *height_map.get(&cell).unwrap_or(&(0, GROUND)))
+                .collect_vec();
+            let landing_height = tops.iter().map(|&(height, _)| height).max().unwrap_or(0);
+
+            supported_by[id] = tops
+                .iter()
+                .filter(|&&(height, _)| height == landing_height)
+                .map(|&(_, supporter)| supporter)
+                .unique()
+                .collect();
+            for &supporter in supported_by[id].iter() {
+                supports[supporter].push(id);
+            }
+
+            for cell in brick.footprint() {
+                height_map.insert(cell, (landing_height + brick.height(), id));
+            }
+        }
+
+        Self { supports, supported_by }
+    }
+
+    fn bricks(&self) -> std::ops::Range<usize> {
+        1..self.supports.len()
+    }
+
+    /// A brick is safe to disintegrate when every brick it supports rests on another one as well.
+    fn count_safe_bricks(&self) -> usize {
+        self.bricks()
+            .filter(|&brick| self.supports[brick].iter().all(|&above| self.supported_by[above].len() > 1))
+            .count()
+    }
+
+    /// A brick falls when a given brick is removed if and only if every path from the ground to it
+    /// goes through that brick, i.e. if the removed brick dominates it in the support graph. The
+    /// falls caused by a brick are thus the size of its subtree in the dominator tree, minus itself.
+    fn count_chain_reaction_falls(&self) -> usize {
+        let brick_count = self.supports.len();
+        let mut dominator = vec![GROUND; brick_count];
+        let mut depth = vec![0; brick_count];
+
+        for brick in self.bricks() {
+            let immediate_dominator = self.supported_by[brick]
+                .iter()
+                .copied()
+                .reduce(|a, b| Self::common_dominator(a, b, &dominator, &depth))
+                .unwrap_or(GROUND);
+            dominator[brick] = immediate_dominator;
+            depth[brick] = depth[immediate_dominator] + 1;
+        }
+
+        let mut subtree_sizes = vec![1; brick_count];
+        for brick in self.bricks().rev() {
+            subtree_sizes[dominator[brick]] += subtree_sizes[brick];
+        }
+
+        self.bricks().map(|brick| subtree_sizes[brick] - 1).sum()
+    }
+
+    fn common_dominator(mut a: usize, mut b: usize, dominator: &[usize], depth: &[usize]) -> usize {
+        while a != b {
+            if depth[a] < depth[b] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            a = dominator[a];
+        }
+
+        a
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use advent_of_code_2023::utils::{get_aoc_input_lines, has_flag};
+use itertools::Itertools;
+
+const DIRECTIONS: [(i64, i64, char); 4] = [(-1, 0, '^'), (1, 0, 'v'), (0, -1, '<'), (0, 1, '>')];
+
+type Position = (usize, usize);
+
+fn main() {
+    let print_route = has_flag("--route");
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let grid = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect_vec())
+        .collect_vec();
+
+    let graph = TrailGraph::from_grid(&grid, true);
+    let (length, route) = graph.find_longest_hike();
+    println!("Longest hike with slopes - Part 1: {}", length);
+    if print_route {
+        print_route_on_grid(&grid, &graph.expand_route(&route));
+    }
+
+    let graph = TrailGraph::from_grid(&grid, false);
+    let (length, route) = graph.find_longest_hike();
+    println!("Longest hike without slopes - Part 2: {}", length);
+    if print_route {
+        print_route_on_grid(&grid, &graph.expand_route(&route));
+    }
+}
+
+#[derive(Debug)]
+struct Edge {
+    to: usize,
+    length: usize,
+    /// Cells walked through after leaving the source junction, up to the destination included.
+    cells: Vec<(usize, usize)>,
+}
+
+/// The trail grid compressed into a graph of junctions (plus the start and the end),
+/// with one edge per corridor linking two of them.
+#[derive(Debug)]
+struct TrailGraph {
+    junctions: Vec<(usize, usize)>,
+    edges: Vec<Vec<Edge>>,
+    start: usize,
+    end: usize,
+}
+
+impl TrailGraph {
+    fn from_grid(grid: &[Vec<char>], respect_slopes: bool) -> Self {
+        let is_open = |(row, column): (i64, i64)| {
+            row >= 0
+                && column >= 0
+                && (row as usize) < grid.len()
+                && (column as usize) < grid[0].len()
+                && grid[row as usize][column as usize] != '#'
+        };
+        let open_neighbours = |(row, column): (usize, usize)| {
+            DIRECTIONS
+                .iter()
+                .filter(|(d_row, d_column, _)| is_open((row as i64 + d_row, column as i64 + d_column)))
+                .count()
+        };
+
+        let start = (0, grid[0].iter().position(|&c| c == '.').expect("Error finding start"));
+        let last_row = grid.len() - 1;
+        let end = (last_row, grid[last_row].iter().position(|&c| c == '.').expect("Error finding end"));
+
+        let mut junctions = vec![start, end];
+        for (row, line) in grid.iter().enumerate() {
+            for (column, &c) in line.iter().enumerate() {
+                if c != '#' && open_neighbours((row, column)) > 2 {
+                    junctions.push((row, column));
+                }
+            }
+        }
+        let junction_ids: HashMap<(usize, usize), usize> =
+            junctions.iter().enumerate().map(|(id, &junction)| (junction, id)).collect();
+
+        let edges = junctions
+            .iter()
+            .map(|&junction| {
+                DIRECTIONS
+                    .iter()
+                    .filter_map(|&direction| {
+                        let (destination, cells) = Self::walk_corridor(grid, junction, direction, respect_slopes, &is_open, &junction_ids)?;
+                        Some(Edge {
+                            to: junction_ids[&destination],
+                            length: cells.len(),
+                            cells,
+                        })
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        Self {
+            junctions,
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+
+    /// Follows a corridor from a junction until the next one, unless it is a dead end
+    /// or a slope forbids going on.
+    fn walk_corridor(
+        grid: &[Vec<char>],
+        from: (usize, usize),
+        direction: (i64, i64, char),
+        respect_slopes: bool,
+        is_open: &impl Fn((i64, i64)) -> bool,
+        junction_ids: &HashMap<(usize, usize), usize>,
+    ) -> Option<(Position, Vec<Position>)> {
+        let mut previous = from;
+        let mut direction = direction;
+        let mut cells = vec![];
+
+        loop {
+            let next = (previous.0 as i64 + direction.0, previous.1 as i64 + direction.1);
+            if !is_open(next) {
+                return None;
+            }
+
+            let next = (next.0 as usize, next.1 as usize);
+            let tile = grid[next.0][next.1];
+            if respect_slopes && tile != '.' && tile != direction.2 {
+                return None;
+            }
+
+            cells.push(next);
+            if junction_ids.contains_key(&next) {
+                return Some((next, cells));
+            }
+
+            direction = *DIRECTIONS.iter().find(|(d_row, d_column, _)| {
+                let candidate = (next.0 as i64 + d_row, next.1 as i64 + d_column);
+                is_open(candidate) && candidate != (previous.0 as i64, previous.1 as i64)
+            })?;
+            previous = next;
+        }
+    }
+
+    /// Depth first search over the junctions, keeping the visited ones in a bitmask.
+    fn find_longest_hike(&self) -> (usize, Vec<usize>) {
+        assert!(self.junctions.len() <= 64, "Too many junctions for a u64 bitmask");
+
+        // The end is only reachable through a single junction: once there, going anywhere
+        // else than the end would make it unreachable
+        let end_gates = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.iter().any(|edge| edge.to == self.end))
+            .map(|(junction, _)| junction)
+            .collect_vec();
+        let end_gate = end_gates.into_iter().exactly_one().ok();
+
+        let mut best = (0, vec![]);
+        let mut route = vec![self.start];
+        self.search(self.start, 1 << self.start, 0, end_gate, &mut route, &mut best);
+        best
+    }
+
+    fn search(
+        &self,
+        junction: usize,
+        visited: u64,
+        length: usize,
+        end_gate: Option<usize>,
+        route: &mut Vec<usize>,
+        best: &mut (usize, Vec<usize>),
+    ) {
+        if junction == self.end {
+            if length > best.0 {
+                *best = (length, route.clone());
+            }
+            return;
+        }
+
+        for edge in self.edges[junction].iter() {
+            if visited & (1 << edge.to) != 0 || (Some(junction) == end_gate && edge.to != self.end) {
+                continue;
+            }
+
+            route.push(edge.to);
+            self.search(edge.to, visited | (1 << edge.to), length + edge.length, end_gate, route, best);
+            route.pop();
+        }
+    }
+
+    /// Turns a route of junctions back into the grid cells walked through.
+    fn expand_route(&self, route: &[usize]) -> Vec<(usize, usize)> {
+        let mut cells = vec![self.junctions[self.start]];
+
+        for (&from, &to) in route.iter().tuple_windows() {
+            let edge = self.edges[from]
+                .iter()
+                .filter(|edge| edge.to == to)
+                .max_by_key(|edge| edge.length)
+                .expect("Error finding edge of route");
+            cells.extend(edge.cells.iter());
+        }
+
+        cells
+    }
+}
+
+fn print_route_on_grid(grid: &[Vec<char>], route: &[(usize, usize)]) {
+    let mut grid = grid.to_vec();
+    for &(row, column) in route {
+        grid[row][column] = 'O';
+    }
+
+    for row in grid.iter() {
+        println!("{}", row.iter().collect::<String>());
+    }
+}
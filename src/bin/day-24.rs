@@ -0,0 +1,205 @@
+use advent_of_code_2023::utils::{get_aoc_input_lines, get_flag_values};
+use itertools::Itertools;
+use num::{rational::Ratio, BigInt, BigRational, Signed, Zero};
+
+// Bounds of the test area, inclusive, on both X and Y axes. The example uses 7 to 27,
+// which can be passed with `--test-area 7 27`.
+const TEST_AREA_MIN: i64 = 200_000_000_000_000;
+const TEST_AREA_MAX: i64 = 400_000_000_000_000;
+// Number of hailstones paired with the first one to build the linear system of part 2.
+// Two are enough in general, more make a degenerate pick unlikely.
+const SYSTEM_HAILSTONES: usize = 4;
+
+fn main() {
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let hailstones = input_lines
+        .map(|line| line.expect("Error reading line"))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Hailstone::from_str(&line))
+        .collect_vec();
+
+    let (min, max) = get_test_area_from_args();
+    let intersections = count_intersections_in_area(&hailstones, min, max);
+    println!("Intersections in test area - Part 1: {}", intersections);
+
+    match find_rock_throw(&hailstones) {
+        Some(rock) => println!("Sum of rock coordinates - Part 2: {}", rock.position.iter().sum::<i64>()),
+        None => println!("Sum of rock coordinates - Part 2: no single trajectory hits every hailstone"),
+    }
+}
+
+fn get_test_area_from_args() -> (i64, i64) {
+    let Some(bounds) = get_flag_values("--test-area", 2) else {
+        return (TEST_AREA_MIN, TEST_AREA_MAX);
+    };
+
+    let bound = |bound: &str| bound.parse::<i64>().expect("Error parsing test area, expected --test-area MIN MAX");
+    (bound(&bounds[0]), bound(&bounds[1]))
+}
+
+// Exact enough for part 1, where products of coordinates and velocities stay far below 2^127
+type Rational128 = Ratio<i128>;
+
+fn rational(value: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(value))
+}
+
+#[derive(Debug, Clone)]
+struct Hailstone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+impl Hailstone {
+    fn from_str(input_line: &str) -> Self {
+        let (position, velocity) = input_line
+            .split('@')
+            .map(|vector| {
+                vector
+                    .split(',')
+                    .map(|value| value.trim().parse::<i64>().expect("Error parsing coordinate"))
+                    .collect_vec()
+                    .try_into()
+                    .expect("Error parsing vector")
+            })
+            .collect_tuple()
+            .expect("Error parsing hailstone");
+
+        Self { position, velocity }
+    }
+
+    /// Where the paths of both hailstones cross on the X/Y plane, if they cross in the future of both.
+    fn intersect_2d(&self, other: &Hailstone) -> Option<(Rational128, Rational128)> {
+        let rational = |value: i64| Rational128::from_integer(value.into());
+        let [x1, y1, _] = self.position.map(rational);
+        let [dx1, dy1, _] = self.velocity.map(rational);
+        let [x2, y2, _] = other.position.map(rational);
+        let [dx2, dy2, _] = other.velocity.map(rational);
+
+        // Parallel paths never cross, or share the whole line which is not counted as a crossing
+        let determinant = dx1 * dy2 - dy1 * dx2;
+        if determinant.is_zero() {
+            return None;
+        }
+
+        let (x, y) = (x2 - x1, y2 - y1);
+        let t1 = (x * dy2 - y * dx2) / determinant;
+        let t2 = (x * dy1 - y * dx1) / determinant;
+        if t1.is_negative() || t2.is_negative() {
+            return None;
+        }
+
+        Some((x1 + t1 * dx1, y1 + t1 * dy1))
+    }
+
+    /// Whether both are at the same position at the same non negative time.
+    fn hits(&self, other: &Hailstone) -> bool {
+        let mut hit_time: Option<BigRational> = None;
+
+        for axis in 0..3 {
+            let distance = other.position[axis] - self.position[axis];
+            let closing_speed = self.velocity[axis] - other.velocity[axis];
+            if closing_speed == 0 {
+                if distance != 0 {
+                    return false;
+                }
+                continue;
+            }
+
+            let time = BigRational::new(distance.into(), closing_speed.into());
+            if time.is_negative() || hit_time.as_ref().is_some_and(|hit_time| *hit_time != time) {
+                return false;
+            }
+            hit_time = Some(time);
+        }
+
+        true
+    }
+}
+
+fn count_intersections_in_area(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    let area = Rational128::from_integer(min.into())..=Rational128::from_integer(max.into());
+
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter_map(|(a, b)| a.intersect_2d(b))
+        .filter(|(x, y)| area.contains(x) && area.contains(y))
+        .count()
+}
+
+fn cross(a: &[BigRational; 3], b: &[BigRational; 3]) -> [BigRational; 3] {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+/// The rock `P + t * V` hits hailstone `p_i + t * v_i` if `(P - p_i) x (V - v_i) = 0`. Expanding
+/// it leaves a `P x V` term shared by every hailstone, which cancels out when subtracting the
+/// equations of two of them, giving 3 linear equations in the 6 unknowns of `P` and `V`:
+/// `P x (v_i - v_j) + (p_i - p_j) x V = p_i x v_i - p_j x v_j`.
+fn find_rock_throw(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let first = hailstones.first()?;
+    let (p0, v0) = (first.position.map(rational), first.velocity.map(rational));
+
+    let mut system = vec![];
+    for hailstone in hailstones.iter().skip(1).take(SYSTEM_HAILSTONES) {
+        let (p, v) = (hailstone.position.map(rational), hailstone.velocity.map(rational));
+        let w = [&v0[0] - &v[0], &v0[1] - &v[1], &v0[2] - &v[2]];
+        let d = [&p0[0] - &p[0], &p0[1] - &p[1], &p0[2] - &p[2]];
+        let [cx, cy, cz] = cross(&p0, &v0);
+        let [ox, oy, oz] = cross(&p, &v);
+        let zero = BigRational::zero;
+
+        // Coefficients of Px, Py, Pz, Vx, Vy, Vz, then the constant term
+        system.push(vec![zero(), w[2].clone(), -&w[1], zero(), -&d[2], d[1].clone(), cx - ox]);
+        system.push(vec![-&w[2], zero(), w[0].clone(), d[2].clone(), zero(), -&d[0], cy - oy]);
+        system.push(vec![w[1].clone(), -&w[0], zero(), -&d[1], d[0].clone(), zero(), cz - oz]);
+    }
+
+    let solution = solve_linear_system(system)?;
+    if solution.iter().any(|value| !value.is_integer()) {
+        return None;
+    }
+
+    let solution = solution.iter().map(|value| i64::try_from(value.to_integer()).ok()).collect::<Option<Vec<_>>>()?;
+    let rock = Hailstone {
+        position: [solution[0], solution[1], solution[2]],
+        velocity: [solution[3], solution[4], solution[5]],
+    };
+
+    hailstones.iter().all(|hailstone| rock.hits(hailstone)).then_some(rock)
+}
+
+/// Gauss-Jordan elimination over an augmented matrix, which may hold more equations than unknowns.
+/// Returns `None` unless the system has exactly one solution.
+fn solve_linear_system(mut rows: Vec<Vec<BigRational>>) -> Option<Vec<BigRational>> {
+    let unknowns = rows.first()?.len() - 1;
+
+    for column in 0..unknowns {
+        let pivot = (column..rows.len()).find(|&row| !rows[row][column].is_zero())?;
+        rows.swap(column, pivot);
+
+        let pivot_row = rows[column].clone();
+        let pivot_value = pivot_row[column].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == column || row[column].is_zero() {
+                continue;
+            }
+
+            let factor = &row[column] / &pivot_value;
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row.iter()) {
+                *value -= &factor * pivot_value;
+            }
+        }
+    }
+
+    // Leftover equations must be consistent with the solution
+    if rows[unknowns..].iter().any(|row| !row[unknowns].is_zero()) {
+        return None;
+    }
+
+    Some(rows[..unknowns].iter().enumerate().map(|(i, row)| &row[unknowns] / &row[i]).collect())
+}
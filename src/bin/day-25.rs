@@ -0,0 +1,150 @@
+use std::collections::{HashMap, VecDeque};
+
+use advent_of_code_2023::utils::{get_aoc_input_lines, has_flag};
+use itertools::Itertools;
+
+fn main() {
+    let print_cut = has_flag("--cut");
+    let input_lines = get_aoc_input_lines().expect("Error getting input");
+    let wiring = Wiring::from_lines(input_lines.map(|line| line.expect("Error reading line")));
+
+    let cut = wiring.find_minimum_cut();
+    let group_size = cut.source_side.len();
+    let product = group_size * (wiring.components.len() - group_size);
+    println!("Group sizes product ({} wires cut) - Part 1: {}", cut.wires.len(), product);
+
+    if print_cut {
+        for &(a, b) in cut.wires.iter() {
+            println!("{}/{}", wiring.components[a], wiring.components[b]);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Cut {
+    source_side: Vec<usize>,
+    wires: Vec<(usize, usize)>,
+}
+
+/// Undirected graph of components, where each wire is an edge of capacity 1 in both directions.
+#[derive(Debug)]
+struct Wiring {
+    components: Vec<String>,
+    wires: Vec<(usize, usize)>,
+    /// `(neighbour, wire)` pairs of each component.
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+impl Wiring {
+    fn from_lines(input_lines: impl Iterator<Item = String>) -> Self {
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut components = vec![];
+        let mut get_id = |name: &str| {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                components.push(name.to_string());
+                components.len() - 1
+            })
+        };
+
+        let mut wires = vec![];
+        for line in input_lines.filter(|line| !line.trim().is_empty()) {
+            let (component, connected) = line.split(':').collect_tuple().expect("Error parsing wiring");
+            let component = get_id(component.trim());
+            for other in connected.split_whitespace() {
+                wires.push((component, get_id(other)));
+            }
+        }
+
+        let mut adjacency = vec![vec![]; components.len()];
+        for (wire, &(a, b)) in wires.iter().enumerate() {
+            adjacency[a].push((b, wire));
+            adjacency[b].push((a, wire));
+        }
+
+        Self {
+            components,
+            wires,
+            adjacency,
+        }
+    }
+
+    /// The global minimum cut separates the first component from at least one other, so it is the
+    /// smallest of the minimum cuts between the first component and each other one, each being the
+    /// maximum flow between them. Augmenting stops as soon as a flow beats the best cut found so far.
+    fn find_minimum_cut(&self) -> Cut {
+        let source = 0;
+        let mut best: Option<(usize, Vec<i32>)> = None;
+
+        for sink in 1..self.components.len() {
+            let limit = best.as_ref().map_or(usize::MAX, |(flow, _)| *flow);
+            let (flow, flows) = self.max_flow(source, sink, limit);
+            if flow < limit {
+                best = Some((flow, flows));
+            }
+        }
+
+        let (_, flows) = best.expect("Error finding a cut, at least two components are needed");
+        let source_side = self.find_reachable(source, &flows).0;
+        let is_source_side = {
+            let mut is_source_side = vec![false; self.components.len()];
+            for &component in source_side.iter() {
+                is_source_side[component] = true;
+            }
+            is_source_side
+        };
+        let wires = self
+            .wires
+            .iter()
+            .copied()
+            .filter(|&(a, b)| is_source_side[a] != is_source_side[b])
+            .collect();
+
+        Cut { source_side, wires }
+    }
+
+    /// Edmonds-Karp, returning the flow and the flow through each wire, positive from its first
+    /// component to its second. Gives up once the flow reaches `limit`.
+    fn max_flow(&self, source: usize, sink: usize, limit: usize) -> (usize, Vec<i32>) {
+        let mut flows = vec![0; self.wires.len()];
+        let mut flow = 0;
+
+        while flow < limit {
+            let (_, parents) = self.find_reachable(source, &flows);
+            if parents[sink].is_none() {
+                break;
+            }
+
+            let mut component = sink;
+            while let Some((previous, wire)) = parents[component] {
+                flows[wire] += if self.wires[wire].0 == previous { 1 } else { -1 };
+                component = previous;
+            }
+            flow += 1;
+        }
+
+        (flow, flows)
+    }
+
+    /// BFS over the wires with residual capacity left, returning the reached components and
+    /// the `(previous component, wire)` each of them was reached through.
+    fn find_reachable(&self, source: usize, flows: &[i32]) -> (Vec<usize>, Vec<Option<(usize, usize)>>) {
+        let mut parents = vec![None; self.components.len()];
+        let mut reached = vec![source];
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(component) = queue.pop_front() {
+            for &(neighbour, wire) in self.adjacency[component].iter() {
+                let flow_to_neighbour = if self.wires[wire].0 == component { flows[wire] } else { -flows[wire] };
+                if neighbour == source || parents[neighbour].is_some() || flow_to_neighbour >= 1 {
+                    continue;
+                }
+
+                parents[neighbour] = Some((component, wire));
+                reached.push(neighbour);
+                queue.push_back(neighbour);
+            }
+        }
+
+        (reached, parents)
+    }
+}
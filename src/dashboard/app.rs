@@ -0,0 +1,98 @@
+use std::{io, time::Duration};
+
+use ratatui::widgets::TableState;
+
+use crate::{
+    registry::{list_days, DayEntry},
+    runner::{benchmark_day, find_expected_answers, run_day, RunHistory, RunRecord, DEFAULT_ANSWER_COUNT},
+};
+
+const BENCHMARK_RUNS: usize = 10;
+
+pub struct App {
+    pub days: Vec<DayEntry>,
+    pub run_history: RunHistory,
+    pub table_state: TableState,
+    pub show_output: bool,
+    pub message: String,
+}
+
+impl App {
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            days: list_days()?,
+            run_history: RunHistory::load()?,
+            table_state: TableState::default().with_selected(0),
+            show_output: false,
+            message: String::new(),
+        })
+    }
+
+    pub fn selected_day(&self) -> &DayEntry {
+        &self.days[self.table_state.selected().unwrap_or(0)]
+    }
+
+    pub fn select_next(&mut self) {
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.table_state.select(Some((selected + 1) % self.days.len()));
+    }
+
+    pub fn select_previous(&mut self) {
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.table_state.select(Some((selected + self.days.len() - 1) % self.days.len()));
+    }
+
+    pub fn refresh(&mut self) {
+        match list_days().and_then(|days| Ok((days, RunHistory::load()?))) {
+            Ok((days, run_history)) => {
+                self.days = days;
+                self.run_history = run_history;
+                self.message = "Refreshed".to_string();
+            }
+            Err(err) => self.message = format!("Error refreshing: {}", err),
+        }
+    }
+
+    pub fn run_selected(&mut self, on_sample_input: bool) {
+        let entry = self.selected_day().clone();
+        let input = if on_sample_input { &entry.sample_input } else { &entry.input };
+
+        let result = find_expected_answers(input).and_then(|expected_answers| {
+            let day_run = run_day(&entry.day, input)?;
+            let answer_count = expected_answers.as_ref().map_or(DEFAULT_ANSWER_COUNT, |expected| expected.len());
+            Ok(RunRecord::from_day_run(&day_run, expected_answers.as_ref(), answer_count))
+        });
+
+        self.message = match result {
+            Ok(record) => {
+                let message = format!(
+                    "Day {} on {}: {} in {:.2?} ({:?})",
+                    entry.day,
+                    input.display(),
+                    record.answers.join(" | "),
+                    record.elapsed,
+                    record.status
+                );
+                self.run_history.record(&entry.day, record);
+                match self.run_history.save() {
+                    Ok(()) => message,
+                    Err(err) => format!("{}, error saving run history: {}", message, err),
+                }
+            }
+            Err(err) => format!("Error running day {}: {}", entry.day, err),
+        };
+    }
+
+    pub fn benchmark_selected(&mut self) {
+        let entry = self.selected_day();
+
+        self.message = match benchmark_day(&entry.day, &entry.input, BENCHMARK_RUNS) {
+            Ok(durations) => {
+                let min = durations.iter().min().copied().unwrap_or_default();
+                let mean = durations.iter().sum::<Duration>() / durations.len() as u32;
+                format!("Day {} over {} runs: min {:.2?}, mean {:.2?}", entry.day, BENCHMARK_RUNS, min, mean)
+            }
+            Err(err) => format!("Error benchmarking day {}: {}", entry.day, err),
+        };
+    }
+}
@@ -0,0 +1,51 @@
+mod app;
+mod ui;
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+
+use app::App;
+
+/// Interactive calendar of the 25 days, see the key bindings in the footer.
+pub fn run_dashboard() -> io::Result<()> {
+    let mut app = App::new()?;
+    let mut terminal = ratatui::init();
+
+    let result = loop {
+        if let Err(err) = terminal.draw(|frame| ui::draw(frame, &mut app)) {
+            break Err(err);
+        }
+
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(err) => break Err(err),
+        };
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break Ok(()),
+            KeyCode::Down | KeyCode::Char('j') => app.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.select_previous(),
+            KeyCode::Char('o') => app.show_output = !app.show_output,
+            KeyCode::Char('r') | KeyCode::Char('s') | KeyCode::Char('b') => {
+                // Runs block the loop, so show what is going on before starting one
+                app.message = format!("Running day {}...", app.selected_day().day);
+                if let Err(err) = terminal.draw(|frame| ui::draw(frame, &mut app)) {
+                    break Err(err);
+                }
+
+                match key.code {
+                    KeyCode::Char('r') => app.run_selected(false),
+                    KeyCode::Char('s') => app.run_selected(true),
+                    _ => app.benchmark_selected(),
+                }
+            }
+            KeyCode::F(5) => app.refresh(),
+            _ => (),
+        }
+    };
+
+    ratatui::restore();
+    result
+}
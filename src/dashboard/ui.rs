@@ -0,0 +1,114 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+
+use super::app::App;
+use crate::{
+    registry::DayEntry,
+    runner::{RunHistory, RunStatus},
+};
+
+const KEY_BINDINGS: &str =
+    "↑/↓ select  r run  s run on sample input  b benchmark  o toggle output  F5 refresh  q quit";
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let output_height = if app.show_output { Constraint::Percentage(40) } else { Constraint::Length(0) };
+    let [table_area, output_area, footer_area] =
+        Layout::vertical([Constraint::Min(5), output_height, Constraint::Length(2)]).areas(frame.area());
+
+    let header = Row::new(["Day", "Solver", "Input", "Sample", "Expected", "Last answers", "Last run", "Time", "Status"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = app.days.iter().map(|entry| day_row(entry, &app.run_history)).collect::<Vec<_>>();
+    let widths = [
+        Constraint::Length(4),
+        Constraint::Length(6),
+        Constraint::Length(7),
+        Constraint::Length(6),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(9),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(" Advent of Code 2023 "))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, table_area, &mut app.table_state);
+
+    if app.show_output {
+        let day = &app.selected_day().day;
+        let record = app.run_history.latest(day);
+        // Stderr comes after the output, in red
+        let output = record
+            .map(|record| {
+                let output = record.output.iter().map(|line| Line::from(line.as_str()));
+                let error_style = Style::default().fg(Color::Red);
+                let errors = record.errors.iter().map(|line| Line::from(line.as_str()).style(error_style));
+                output.chain(errors).collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let title = match record {
+            Some(record) => format!(" Last output of day {} on {} ", day, record.input.display()),
+            None => format!(" Last output of day {} ", day),
+        };
+        let output = Paragraph::new(output).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(output, output_area);
+    }
+
+    let footer = Paragraph::new(vec![Line::from(app.message.as_str()), Line::from(KEY_BINDINGS).style(Style::default().fg(Color::DarkGray))]);
+    frame.render_widget(footer, footer_area);
+}
+
+fn day_row<'a>(entry: &'a DayEntry, run_history: &RunHistory) -> Row<'a> {
+    let presence = |present: bool| if present { "✓" } else { "·" };
+    let input = match (entry.has_input, entry.is_input_locked) {
+        (true, true) => "locked",
+        (true, false) => "✓",
+        (false, _) => "·",
+    };
+    let expected_answers = entry.expected_answers.as_ref().map(|answers| answers.join(" | ")).unwrap_or_default();
+
+    // Expected answers are those of the main input, so only its run is compared with them
+    let record = run_history.get(&entry.day, &entry.input);
+    let last_answers = record.map(|record| record.answers.join(" | ")).unwrap_or_default();
+    let last_run = record.map(|record| format_ago(record.finished_at)).unwrap_or_default();
+    let elapsed = record.map(|record| format!("{:.2?}", record.elapsed)).unwrap_or_default();
+    let (status, color) = match record.map(|record| record.status) {
+        Some(RunStatus::Passed) => ("pass", Color::Green),
+        Some(RunStatus::Mismatch) => ("mismatch", Color::Red),
+        Some(RunStatus::Crashed) => ("crashed", Color::Red),
+        Some(RunStatus::Unchecked) => ("unchecked", Color::Yellow),
+        None => ("", Color::Reset),
+    };
+
+    Row::new(vec![
+        Line::from(entry.day.as_str()),
+        Line::from(presence(entry.has_solver)),
+        Line::from(input),
+        Line::from(presence(entry.has_sample_input)),
+        Line::from(expected_answers),
+        Line::from(last_answers),
+        Line::from(last_run),
+        Line::from(elapsed),
+        Line::from(status).style(Style::default().fg(color)),
+    ])
+}
+
+fn format_ago(timestamp: u64) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let seconds = now.saturating_sub(timestamp);
+
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
@@ -0,0 +1,79 @@
+use std::io;
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+const MAGIC: &[u8] = b"AOCENC01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Encrypts `plaintext` with a key derived from `passphrase` (Argon2id, ChaCha20-Poly1305).
+/// The output is laid out as `MAGIC | salt | nonce | ciphertext`.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> io::Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = derive_cipher(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| io::Error::other("Error encrypting input"))?;
+
+    Ok([MAGIC, &salt, nonce.as_slice(), &ciphertext].concat())
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> io::Result<Vec<u8>> {
+    let data = data
+        .strip_prefix(MAGIC)
+        .filter(|data| data.len() >= SALT_LEN + NONCE_LEN)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Not an encrypted input file"))?;
+    let (salt, data) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+    derive_cipher(passphrase, salt)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Error decrypting input, is the passphrase right?"))
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> io::Result<ChaCha20Poly1305> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+    Ok(ChaCha20Poly1305::new(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt_reverses_encrypt() {
+        let encrypted = encrypt(b"1abc2\npqr3stu8vwx\n", "passphrase").unwrap();
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(decrypt(&encrypted, "passphrase").unwrap(), b"1abc2\npqr3stu8vwx\n");
+    }
+
+    #[test]
+    fn encrypt_salts_every_file() {
+        assert_ne!(encrypt(b"input", "passphrase").unwrap(), encrypt(b"input", "passphrase").unwrap());
+    }
+
+    #[test]
+    fn decrypt_rejects_a_wrong_passphrase() {
+        let encrypted = encrypt(b"input", "passphrase").unwrap();
+        let err = decrypt(&encrypted, "wrong passphrase").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("passphrase"));
+    }
+
+    #[test]
+    fn decrypt_rejects_plain_files() {
+        let err = decrypt(b"1abc2\n", "passphrase").unwrap_err();
+        assert_eq!(err.to_string(), "Not an encrypted input file");
+    }
+}
@@ -0,0 +1,36 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{decrypt, encrypt, get_encrypted_path};
+
+/// Encrypts every `.txt` file of `dir` into a `.txt.enc` file next to it.
+/// Files whose `.enc` counterpart already holds the same content are left untouched,
+/// so that re-locking does not churn the committed files.
+pub fn lock_inputs(dir: &Path, passphrase: &str) -> io::Result<Vec<PathBuf>> {
+    let mut locked_files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+
+        let plaintext = fs::read(&path)?;
+        let encrypted_path = get_encrypted_path(&path);
+        let is_up_to_date = fs::read(&encrypted_path)
+            .ok()
+            .and_then(|data| decrypt(&data, passphrase).ok())
+            .is_some_and(|decrypted| decrypted == plaintext);
+        if is_up_to_date {
+            continue;
+        }
+
+        fs::write(&encrypted_path, encrypt(&plaintext, passphrase)?)?;
+        locked_files.push(encrypted_path);
+    }
+
+    locked_files.sort();
+    Ok(locked_files)
+}
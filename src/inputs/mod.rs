@@ -0,0 +1,8 @@
+mod cipher;
+mod lock_inputs;
+mod read_input_file;
+mod unlock_inputs;
+pub use cipher::{decrypt, encrypt};
+pub use lock_inputs::lock_inputs;
+pub use read_input_file::{get_encrypted_path, get_inputs_key, read_input_file, INPUTS_KEY_VAR};
+pub use unlock_inputs::unlock_inputs;
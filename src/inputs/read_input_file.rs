@@ -0,0 +1,90 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Cursor},
+    path::{Path, PathBuf},
+};
+
+use super::decrypt;
+
+pub const INPUTS_KEY_VAR: &str = "AOC_INPUTS_KEY";
+
+pub fn get_inputs_key() -> Option<String> {
+    env::var(INPUTS_KEY_VAR).ok().filter(|key| !key.is_empty())
+}
+
+pub fn get_encrypted_path(path: &Path) -> PathBuf {
+    let mut encrypted_path = path.as_os_str().to_owned();
+    encrypted_path.push(".enc");
+    PathBuf::from(encrypted_path)
+}
+
+/// Opens an input file, falling back to decrypting its `.enc` counterpart in memory
+/// when only the locked version is present.
+pub fn read_input_file(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let encrypted_path = get_encrypted_path(path);
+    if path.exists() || !encrypted_path.is_file() {
+        return Ok(Box::new(BufReader::new(File::open(path)?)));
+    }
+
+    let passphrase = get_inputs_key().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is locked, set {} to decrypt it", path.display(), INPUTS_KEY_VAR),
+        )
+    })?;
+    let plaintext = decrypt(&fs::read(encrypted_path)?, &passphrase)?;
+
+    Ok(Box::new(Cursor::new(plaintext)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, process};
+
+    use super::*;
+    use crate::inputs::encrypt;
+
+    fn temp_input(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-read-input-file-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn read_to_string(path: &Path) -> io::Result<String> {
+        let mut content = String::new();
+        read_input_file(path)?.read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    #[test]
+    fn plain_input_is_read_as_is() {
+        let path = temp_input("plain.txt");
+        fs::write(&path, "plain\n").unwrap();
+        fs::write(get_encrypted_path(&path), encrypt(b"locked\n", "passphrase").unwrap()).unwrap();
+
+        assert_eq!(read_to_string(&path).unwrap(), "plain\n");
+    }
+
+    #[test]
+    fn locked_input_is_decrypted_with_the_key() {
+        let path = temp_input("locked.txt");
+        fs::write(get_encrypted_path(&path), encrypt(b"locked\n", "passphrase").unwrap()).unwrap();
+
+        // The only test setting the key, so that tests running in parallel do not race on it
+        env::remove_var(INPUTS_KEY_VAR);
+        assert_eq!(read_to_string(&path).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+
+        env::set_var(INPUTS_KEY_VAR, "wrong passphrase");
+        assert_eq!(read_to_string(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        env::set_var(INPUTS_KEY_VAR, "passphrase");
+        assert_eq!(read_to_string(&path).unwrap(), "locked\n");
+    }
+
+    #[test]
+    fn missing_input_is_not_found() {
+        let path = temp_input("missing.txt");
+        assert_eq!(read_to_string(&path).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
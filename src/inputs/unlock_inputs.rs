@@ -0,0 +1,25 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::decrypt;
+
+/// Decrypts every `.enc` file of `dir` back next to it, overwriting the plain files.
+pub fn unlock_inputs(dir: &Path, passphrase: &str) -> io::Result<Vec<PathBuf>> {
+    let mut unlocked_files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|extension| extension != "enc") {
+            continue;
+        }
+
+        let plain_path = path.with_extension("");
+        fs::write(&plain_path, decrypt(&fs::read(&path)?, passphrase)?)?;
+        unlocked_files.push(plain_path);
+    }
+
+    unlocked_files.sort();
+    Ok(unlocked_files)
+}
@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, io};
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use super::Leaderboard;
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC, from the 1st of December.
+const UNLOCK_HOUR_UTC: i64 = 5;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Serialize)]
+pub struct Standing {
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    pub local_score: u64,
+    pub reported_local_score: u64,
+    pub days: BTreeMap<u32, DayCompletion>,
+}
+
+/// Star timestamps of a day, along with the time taken since the puzzle unlocked.
+#[derive(Debug, Serialize)]
+pub struct DayCompletion {
+    pub part_1_ts: Option<i64>,
+    pub part_2_ts: Option<i64>,
+    pub part_1_time: Option<i64>,
+    pub part_2_time: Option<i64>,
+    pub part_2_delta: Option<i64>,
+}
+
+impl DayCompletion {
+    fn new(first_unlock_ts: i64, day: u32, part_1_ts: Option<i64>, part_2_ts: Option<i64>) -> Self {
+        let unlock_ts = first_unlock_ts + (day as i64 - 1) * SECONDS_PER_DAY;
+
+        Self {
+            part_1_ts,
+            part_2_ts,
+            part_1_time: part_1_ts.map(|ts| ts - unlock_ts),
+            part_2_time: part_2_ts.map(|ts| ts - unlock_ts),
+            part_2_delta: part_1_ts.zip(part_2_ts).map(|(part_1_ts, part_2_ts)| part_2_ts - part_1_ts),
+        }
+    }
+}
+
+/// Recomputes the local scores from the star timestamps: for every star, the first member
+/// to get it scores as many points as there are members, the second one point less, and so on.
+/// Standings are sorted by local score, then by the time of the last star.
+pub fn compute_standings(leaderboard: &Leaderboard) -> io::Result<Vec<Standing>> {
+    let year = leaderboard.event.parse::<i64>().map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid leaderboard event '{}'", leaderboard.event))
+    })?;
+    let first_unlock_ts = get_first_unlock_ts(year);
+
+    let members = leaderboard.members.values().collect_vec();
+    let mut local_scores = vec![0; members.len()];
+
+    for day in 1..=25 {
+        for part in 1..=2 {
+            let finishers = members
+                .iter()
+                .enumerate()
+                .filter_map(|(i, member)| {
+                    let completion = member.completion_day_level.get(&day.to_string())?.get(&part.to_string())?;
+                    Some((completion.get_star_ts, completion.star_index, i))
+                })
+                .sorted();
+
+            for (rank, (_, _, i)) in finishers.enumerate() {
+                local_scores[i] += (members.len() - rank) as u64;
+            }
+        }
+    }
+
+    let standings = members
+        .iter()
+        .zip(local_scores)
+        .map(|(member, local_score)| Standing {
+            id: member.id,
+            name: member.display_name(),
+            stars: member.stars,
+            local_score,
+            reported_local_score: member.local_score,
+            days: (1..=25)
+                .filter_map(|day| {
+                    let part_1_ts = member.get_star_ts(day, 1);
+                    let part_2_ts = member.get_star_ts(day, 2);
+                    part_1_ts.map(|_| (day, DayCompletion::new(first_unlock_ts, day, part_1_ts, part_2_ts)))
+                })
+                .collect(),
+        })
+        .sorted_by_key(|standing| {
+            let last_star_ts = standing
+                .days
+                .values()
+                .flat_map(|day| [day.part_1_ts, day.part_2_ts])
+                .flatten()
+                .max()
+                .unwrap_or(i64::MAX);
+            (std::cmp::Reverse(standing.local_score), last_star_ts, standing.id)
+        })
+        .collect();
+
+    Ok(standings)
+}
+
+/// Unlock time of day 1 of an event, i.e. December 1st of its year at 05:00 UTC.
+fn get_first_unlock_ts(year: i64) -> i64 {
+    // Days from 1970-01-01 to December 1st, counting years from March so leap days come last
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (12 - 3) + 2) / 5;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * SECONDS_PER_DAY + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Formats a number of seconds the way the leaderboard does, e.g. `01:02:03`.
+pub fn format_duration(seconds: i64) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 1 of 2022 unlocked at 1669870800
+    const LEADERBOARD_JSON: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 2, "local_score": 0,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669870900, "star_index": 10 },
+                        "2": { "get_star_ts": 1669871000, "star_index": 30 }
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": "bob", "stars": 2, "local_score": 0,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669870850, "star_index": 5 },
+                        "2": { "get_star_ts": 1669871100, "star_index": 40 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": null, "stars": 1, "local_score": 0,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669871200, "star_index": 50 } }
+                }
+            },
+            "4": { "id": 4, "name": "dan", "stars": 0, "local_score": 0, "completion_day_level": {} }
+        }
+    }"#;
+
+    #[test]
+    fn first_unlock_follows_the_event() {
+        assert_eq!(get_first_unlock_ts(2023), 1_701_406_800);
+        assert_eq!(get_first_unlock_ts(2022), 1_669_870_800);
+        assert_eq!(get_first_unlock_ts(2015), 1_448_946_000);
+    }
+
+    #[test]
+    fn standings_are_ranked_by_score_then_last_star() {
+        let leaderboard: Leaderboard = LEADERBOARD_JSON.parse().unwrap();
+        let standings = compute_standings(&leaderboard).unwrap();
+
+        // Alice and Bob both score 3 + 4 points out of 4 per star, Alice getting her last star first
+        let ranking = standings.iter().map(|standing| (standing.name.as_str(), standing.local_score)).collect_vec();
+        assert_eq!(ranking, vec![("alice", 7), ("bob", 7), ("(anonymous user #3)", 2), ("dan", 0)]);
+    }
+
+    #[test]
+    fn day_times_start_at_the_unlock_of_the_event() {
+        let leaderboard: Leaderboard = LEADERBOARD_JSON.parse().unwrap();
+        let standings = compute_standings(&leaderboard).unwrap();
+
+        let bob = &standings[1].days[&1];
+        assert_eq!((bob.part_1_time, bob.part_2_time, bob.part_2_delta), (Some(50), Some(300), Some(250)));
+        assert_eq!(standings[2].days[&1].part_2_time, None);
+    }
+
+    #[test]
+    fn invalid_event_is_an_error() {
+        let json = LEADERBOARD_JSON.replace(r#""event": "2022""#, r#""event": "next year""#);
+        let leaderboard: Leaderboard = json.parse().unwrap();
+        assert_eq!(compute_standings(&leaderboard).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,49 @@
+use std::{collections::HashMap, io, str::FromStr};
+
+use serde::Deserialize;
+
+/// Private leaderboard as served by `/2023/leaderboard/private/view/<ID>.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Keyed by day, then by part (`"1"` or `"2"`).
+    pub completion_day_level: HashMap<String, HashMap<String, StarCompletion>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StarCompletion {
+    pub get_star_ts: i64,
+    pub star_index: u64,
+}
+
+impl FromStr for Leaderboard {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        serde_json::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl Member {
+    /// Anonymous members show up without a name on the website.
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn get_star_ts(&self, day: u32, part: u32) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())
+            .and_then(|parts| parts.get(&part.to_string()))
+            .map(|completion| completion.get_star_ts)
+    }
+}
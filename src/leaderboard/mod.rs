@@ -0,0 +1,4 @@
+mod compute_standings;
+mod leaderboard_json;
+pub use compute_standings::{compute_standings, format_duration, DayCompletion, Standing};
+pub use leaderboard_json::{Leaderboard, Member, StarCompletion};
@@ -1 +1,8 @@
+pub mod dashboard;
+pub mod inputs;
+pub mod leaderboard;
+pub mod puzzles;
+pub mod registry;
+pub mod runner;
+pub mod scratchcards;
 pub mod utils;
\ No newline at end of file
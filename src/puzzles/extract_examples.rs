@@ -0,0 +1,29 @@
+use scraper::{ElementRef, Html, Selector};
+
+use super::ARTICLE_SELECTOR;
+
+/// Returns the distinct code blocks that the puzzle text introduces as an example.
+pub fn extract_examples(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(ARTICLE_SELECTOR).unwrap();
+
+    let mut examples: Vec<String> = Vec::new();
+    for article in document.select(&selector) {
+        let mut previous_paragraph = String::new();
+
+        for child in article.children().filter_map(ElementRef::wrap) {
+            match child.value().name() {
+                "p" => previous_paragraph = child.text().collect::<String>().to_lowercase(),
+                "pre" if previous_paragraph.contains("example") => {
+                    let example = child.text().collect::<String>().trim_end().to_string() + "\n";
+                    if !examples.contains(&example) {
+                        examples.push(example);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    examples
+}
@@ -0,0 +1,8 @@
+mod extract_examples;
+mod puzzle_to_markdown;
+mod render_markdown;
+pub use extract_examples::extract_examples;
+pub use puzzle_to_markdown::puzzle_to_markdown;
+pub use render_markdown::render_markdown;
+
+const ARTICLE_SELECTOR: &str = "article.day-desc";
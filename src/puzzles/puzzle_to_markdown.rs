@@ -0,0 +1,70 @@
+use scraper::{ElementRef, Html, Node, Selector};
+
+use super::ARTICLE_SELECTOR;
+
+/// Converts the puzzle articles of a day page (one per part) into markdown. Links relative to
+/// the site are made absolute with `base_url`, the site the page was fetched from.
+pub fn puzzle_to_markdown(html: &str, base_url: &str) -> String {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(ARTICLE_SELECTOR).unwrap();
+
+    document
+        .select(&selector)
+        .map(|article| blocks_to_markdown(article, base_url).trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+fn blocks_to_markdown(element: ElementRef, base_url: &str) -> String {
+    let mut markdown = String::new();
+
+    for child in element.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "h2" => markdown += &format!("## {}\n\n", inline_to_markdown(child, base_url).trim_matches('-').trim()),
+            "p" => markdown += &format!("{}\n\n", inline_to_markdown(child, base_url).trim()),
+            "pre" => {
+                let code = child.text().collect::<String>();
+                markdown += &format!("```\n{}\n```\n\n", code.trim_end());
+            }
+            "ul" | "ol" => {
+                for item in child.children().filter_map(ElementRef::wrap) {
+                    markdown += &format!("- {}\n", inline_to_markdown(item, base_url).trim());
+                }
+                markdown += "\n";
+            }
+            _ => markdown += &blocks_to_markdown(child, base_url),
+        }
+    }
+
+    markdown
+}
+
+fn inline_to_markdown(element: ElementRef, base_url: &str) -> String {
+    let mut markdown = String::new();
+
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => markdown += &text.replace('\n', " "),
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).unwrap();
+                match child.value().name() {
+                    "code" => markdown += &format!("`{}`", child.text().collect::<String>()),
+                    "em" => markdown += &format!("**{}**", inline_to_markdown(child, base_url)),
+                    "a" => match child.value().attr("href") {
+                        Some(href) if href.starts_with('/') => {
+                            markdown += &format!("[{}]({}{})", inline_to_markdown(child, base_url), base_url.trim_end_matches('/'), href)
+                        }
+                        Some(href) => markdown += &format!("[{}]({})", inline_to_markdown(child, base_url), href),
+                        None => markdown += &inline_to_markdown(child, base_url),
+                    },
+                    "br" => markdown += "  \n",
+                    _ => markdown += &inline_to_markdown(child, base_url),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    markdown
+}
@@ -0,0 +1,31 @@
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Light ANSI styling of the puzzle markdown: bold headings and emphasis, coloured code blocks.
+pub fn render_markdown(markdown: &str) -> String {
+    let mut rendered = String::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            rendered += &format!("    {}{}{}\n", CYAN, line, RESET);
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            rendered += &format!("{}{}{}\n", BOLD, heading, RESET);
+        } else {
+            let line = line
+                .split("**")
+                .enumerate()
+                .map(|(i, part)| if i % 2 == 1 { format!("{}{}{}", BOLD, part, RESET) } else { part.to_string() })
+                .collect::<String>();
+            rendered += &format!("{}\n", line);
+        }
+    }
+
+    rendered
+}
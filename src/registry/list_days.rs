@@ -0,0 +1,38 @@
+use std::{io, path::PathBuf};
+
+use crate::{inputs::get_encrypted_path, runner::find_expected_answers};
+
+/// What the repository holds for a given day, relative to its root.
+#[derive(Debug, Clone)]
+pub struct DayEntry {
+    pub day: String,
+    pub has_solver: bool,
+    pub input: PathBuf,
+    pub has_input: bool,
+    pub is_input_locked: bool,
+    pub sample_input: PathBuf,
+    pub has_sample_input: bool,
+    pub expected_answers: Option<Vec<String>>,
+}
+
+pub fn list_days() -> io::Result<Vec<DayEntry>> {
+    (1..=25)
+        .map(|day| {
+            let day = format!("{:02}", day);
+            let input = PathBuf::from(format!("inputs/day-{}.txt", day));
+            let sample_input = PathBuf::from(format!("sample-inputs/day-{}.txt", day));
+            let is_input_locked = !input.is_file() && get_encrypted_path(&input).is_file();
+
+            Ok(DayEntry {
+                has_solver: PathBuf::from(format!("src/bin/day-{}.rs", day)).is_file(),
+                has_input: input.is_file() || is_input_locked,
+                is_input_locked,
+                has_sample_input: sample_input.is_file(),
+                expected_answers: find_expected_answers(&input)?,
+                day,
+                input,
+                sample_input,
+            })
+        })
+        .collect()
+}
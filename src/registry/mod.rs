@@ -0,0 +1,2 @@
+mod list_days;
+pub use list_days::{list_days, DayEntry};
@@ -0,0 +1,16 @@
+use std::{io, path::Path, time::Duration};
+
+use super::run_day;
+
+/// Runs a day `runs` times on the same input and returns the duration of every run.
+pub fn benchmark_day(day: &str, input: &Path, runs: usize) -> io::Result<Vec<Duration>> {
+    (0..runs)
+        .map(|_| {
+            let day_run = run_day(day, input)?;
+            if !day_run.success {
+                return Err(io::Error::other(format!("day-{} failed on {}", day, input.display())));
+            }
+            Ok(day_run.elapsed)
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::ANSWERS_FILE_NAME;
+use crate::inputs::get_encrypted_path;
+
+/// Resolves a file, a directory or a glob pattern into a sorted list of input files.
+/// Locked inputs are listed under their plain name, the day binaries decrypt them on the fly.
+pub fn collect_input_files(pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+
+    let files = if path.is_file() || get_encrypted_path(path).is_file() {
+        vec![path.to_path_buf()]
+    } else if path.is_dir() {
+        fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?
+    } else {
+        // Locked inputs only match the pattern once their `.enc` extension is part of it
+        let locked_pattern = format!("{}.enc", pattern);
+        [pattern, locked_pattern.as_str()]
+            .iter()
+            .map(|pattern| glob::glob(pattern).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err)))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.map_err(io::Error::from))
+            .collect::<io::Result<Vec<_>>>()?
+    };
+
+    let mut files = files
+        .into_iter()
+        .map(|file| match file.extension() {
+            Some(extension) if extension == "enc" => file.with_extension(""),
+            _ => file,
+        })
+        .filter(|file| (file.is_file() || get_encrypted_path(file).is_file()) && !is_ignored(file))
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+
+    if files.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No input files found for '{}'", pattern),
+        ));
+    }
+
+    Ok(files)
+}
+
+fn is_ignored(file: &Path) -> bool {
+    let file_name = file.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    file_name == ANSWERS_FILE_NAME || file_name.starts_with('.')
+}
@@ -0,0 +1,69 @@
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// Expected answers keyed by input file name, e.g. `"day-01.txt" = [142, 281]`.
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: HashMap<String, Vec<String>>,
+}
+
+impl FromStr for ExpectedAnswers {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        let table = s
+            .parse::<toml::Table>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let answers = table
+            .into_iter()
+            .map(|(file_name, value)| {
+                let values = match value {
+                    toml::Value::Array(values) => values,
+                    value => vec![value],
+                };
+                let values = values.iter().map(format_answer).collect::<io::Result<Vec<_>>>()?;
+                Ok((file_name, values))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { answers })
+    }
+}
+
+impl ExpectedAnswers {
+    pub fn get(&self, input: &Path) -> Option<&Vec<String>> {
+        let file_name = input.file_name()?.to_str()?;
+        self.answers.get(file_name)
+    }
+}
+
+/// Loads the `answers.toml` stored in `dir`, if there is one.
+pub fn load_expected_answers(dir: &Path) -> io::Result<Option<ExpectedAnswers>> {
+    let path = dir.join(ANSWERS_FILE_NAME);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    fs::read_to_string(path)?.parse().map(Some)
+}
+
+fn format_answer(value: &toml::Value) -> io::Result<String> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        value => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unsupported answer value: {}", value),
+        )),
+    }
+}
+
+/// Looks up the expected answers of an input in the `answers.toml` next to it.
+pub fn find_expected_answers(input: &Path) -> io::Result<Option<Vec<String>>> {
+    let dir = input.parent().unwrap_or(Path::new("."));
+    let expected_answers = load_expected_answers(dir)?;
+
+    Ok(expected_answers.and_then(|expected_answers| expected_answers.get(input).cloned()))
+}
@@ -0,0 +1,10 @@
+mod benchmark_day;
+mod collect_input_files;
+mod expected_answers;
+mod run_day;
+mod run_history;
+pub use benchmark_day::benchmark_day;
+pub use collect_input_files::collect_input_files;
+pub use expected_answers::{find_expected_answers, load_expected_answers, ExpectedAnswers, ANSWERS_FILE_NAME};
+pub use run_day::{get_day_binary_path, run_day, DayRun, RunStatus, DEFAULT_ANSWER_COUNT};
+pub use run_history::{RunHistory, RunRecord};
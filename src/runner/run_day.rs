@@ -0,0 +1,97 @@
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Binaries print their answers last, usually one line per part.
+pub const DEFAULT_ANSWER_COUNT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunStatus {
+    Passed,
+    Mismatch,
+    Crashed,
+    Unchecked,
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub input: PathBuf,
+    pub output: Vec<String>,
+    /// Lines written to stderr, where binaries report errors and warnings.
+    pub errors: Vec<String>,
+    pub elapsed: Duration,
+    pub success: bool,
+}
+
+impl DayRun {
+    /// Returns the last `count` output lines, keeping only what follows their final `: `.
+    pub fn answers(&self, count: usize) -> Vec<&str> {
+        self.output
+            .iter()
+            .skip(self.output.len().saturating_sub(count))
+            .map(|line| line.rsplit(": ").next().unwrap_or(line).trim())
+            .collect()
+    }
+
+    /// Last line written to stderr, usually the reason why the binary failed. The hint that
+    /// follows the message of a panic is skipped.
+    pub fn last_error(&self) -> Option<&str> {
+        self.errors.iter().rev().find(|line| !line.starts_with("note: ")).map(|line| line.as_str())
+    }
+
+    pub fn check(&self, expected_answers: Option<&Vec<String>>) -> RunStatus {
+        if !self.success {
+            return RunStatus::Crashed;
+        }
+
+        match expected_answers {
+            Some(expected) if *expected == self.answers(expected.len()) => RunStatus::Passed,
+            Some(_) => RunStatus::Mismatch,
+            None => RunStatus::Unchecked,
+        }
+    }
+}
+
+/// The day binaries are built next to the `aoc` binary by `cargo build`.
+pub fn get_day_binary_path(day: &str) -> io::Result<PathBuf> {
+    let binary_name = format!("day-{}{}", day, env::consts::EXE_SUFFIX);
+    let path = env::current_exe()?.with_file_name(&binary_name);
+
+    if !path.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found, build it with `cargo build --bin day-{}`", path.display(), day),
+        ));
+    }
+
+    Ok(path)
+}
+
+pub fn run_day(day: &str, input: &Path) -> io::Result<DayRun> {
+    let binary = get_day_binary_path(day)?;
+
+    let start = Instant::now();
+    let output = Command::new(binary).arg(input).env("RUST_BACKTRACE", "0").output()?;
+    let elapsed = start.elapsed();
+
+    Ok(DayRun {
+        input: input.to_path_buf(),
+        output: non_empty_lines(&output.stdout),
+        errors: non_empty_lines(&output.stderr),
+        elapsed,
+        success: output.status.success(),
+    })
+}
+
+fn non_empty_lines(bytes: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
@@ -0,0 +1,79 @@
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{DayRun, RunStatus};
+
+const RUN_HISTORY_FILE_NAME: &str = "aoc-runs.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub input: PathBuf,
+    pub answers: Vec<String>,
+    pub output: Vec<String>,
+    pub errors: Vec<String>,
+    pub elapsed: Duration,
+    pub status: RunStatus,
+    /// Seconds since the Unix epoch.
+    pub finished_at: u64,
+}
+
+impl RunRecord {
+    pub fn from_day_run(day_run: &DayRun, expected_answers: Option<&Vec<String>>, answer_count: usize) -> Self {
+        Self {
+            input: day_run.input.clone(),
+            answers: day_run.answers(answer_count).iter().map(|answer| answer.to_string()).collect(),
+            output: day_run.output.clone(),
+            errors: day_run.errors.clone(),
+            elapsed: day_run.elapsed,
+            status: day_run.check(expected_answers),
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+        }
+    }
+}
+
+/// Last run of every day on each of its inputs, stored next to the binaries so that each build
+/// profile keeps its own.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RunHistory {
+    runs: BTreeMap<String, BTreeMap<PathBuf, RunRecord>>,
+}
+
+impl RunHistory {
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(get_run_history_path()?) {
+            Ok(json) => serde_json::from_str(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(get_run_history_path()?, json)
+    }
+
+    pub fn get(&self, day: &str, input: &Path) -> Option<&RunRecord> {
+        self.runs.get(day)?.get(input)
+    }
+
+    /// Most recent run of the day, whatever its input.
+    pub fn latest(&self, day: &str) -> Option<&RunRecord> {
+        self.runs.get(day)?.values().max_by_key(|record| record.finished_at)
+    }
+
+    pub fn record(&mut self, day: &str, record: RunRecord) {
+        self.runs.entry(day.to_string()).or_default().insert(record.input.clone(), record);
+    }
+}
+
+fn get_run_history_path() -> io::Result<PathBuf> {
+    Ok(env::current_exe()?.with_file_name(RUN_HISTORY_FILE_NAME))
+}
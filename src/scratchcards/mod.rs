@@ -0,0 +1,6 @@
+mod play_scratchcards;
+mod scoring_rule;
+mod scratchcard;
+pub use play_scratchcards::{play_scratchcards, CardOutcome};
+pub use scoring_rule::ScoringRule;
+pub use scratchcard::Scratchcard;
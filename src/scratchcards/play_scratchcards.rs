@@ -0,0 +1,45 @@
+use super::Scratchcard;
+
+/// What a card of the pile ended up doing once every copy of it was scratched.
+#[derive(Debug, Clone)]
+pub struct CardOutcome {
+    pub id: usize,
+    pub matches: usize,
+    /// Instances scratched, the original card included.
+    pub copies: u64,
+    /// Indices of the cards each instance wins a copy of.
+    pub wins: Vec<usize>,
+    /// Indices of the cards that won copies of this one, with how many copies each of them won.
+    /// Together they form the contribution tree of the pile.
+    pub spawned_by: Vec<(usize, u64)>,
+}
+
+/// Every card wins a copy of the `matches` cards following it, and so does every copy of it.
+/// Cards only win cards below them, so copies are settled in a single pass from the top.
+/// Wins past the end of the pile are dropped.
+pub fn play_scratchcards(cards: &[Scratchcard]) -> Vec<CardOutcome> {
+    let mut outcomes: Vec<CardOutcome> = cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| {
+            let matches = card.matches();
+            CardOutcome {
+                id: card.id,
+                matches,
+                copies: 1,
+                wins: (i + 1..(i + 1 + matches).min(cards.len())).collect(),
+                spawned_by: vec![],
+            }
+        })
+        .collect();
+
+    for i in 0..outcomes.len() {
+        let copies = outcomes[i].copies;
+        for won in outcomes[i].wins.clone() {
+            outcomes[won].copies += copies;
+            outcomes[won].spawned_by.push((i, copies));
+        }
+    }
+
+    outcomes
+}
@@ -0,0 +1,49 @@
+use std::{io, str::FromStr};
+
+/// How many points a card is worth for its number of matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringRule {
+    /// 1 point for the first match, doubled for each other one, as in the puzzle.
+    Doubling,
+    /// 1 point per match.
+    Linear,
+    /// 1, 1, 2, 3, 5, ... points for 1, 2, 3, 4, 5, ... matches.
+    Fibonacci,
+    /// Doubling, but never more than the given number of points.
+    Capped(u64),
+}
+
+impl FromStr for ScoringRule {
+    type Err = io::Error;
+
+    /// Parses `doubling`, `linear`, `fibonacci` or `capped:<POINTS>`.
+    fn from_str(s: &str) -> io::Result<Self> {
+        match s.split_once(':') {
+            None if s == "doubling" => Ok(Self::Doubling),
+            None if s == "linear" => Ok(Self::Linear),
+            None if s == "fibonacci" => Ok(Self::Fibonacci),
+            Some(("capped", cap)) => cap.parse().map(Self::Capped).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid scoring cap: {}", cap))
+            }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown scoring rule: {}, expected doubling, linear, fibonacci or capped:<POINTS>", s),
+            )),
+        }
+    }
+}
+
+impl ScoringRule {
+    pub fn score(&self, matches: usize) -> u64 {
+        if matches == 0 {
+            return 0;
+        }
+
+        match self {
+            Self::Doubling => 1u64.checked_shl(matches as u32 - 1).unwrap_or(u64::MAX),
+            Self::Linear => matches as u64,
+            Self::Fibonacci => (1..matches).fold((1u64, 0u64), |(a, b), _| (a.saturating_add(b), a)).0,
+            Self::Capped(cap) => Self::Doubling.score(matches).min(*cap),
+        }
+    }
+}
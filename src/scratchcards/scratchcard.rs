@@ -0,0 +1,45 @@
+use std::{collections::HashSet, io, str::FromStr};
+
+/// A card such as `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+#[derive(Debug, Clone)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning_numbers: HashSet<u32>,
+    pub numbers: HashSet<u32>,
+}
+
+impl FromStr for Scratchcard {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", message, s));
+
+        let (header, numbers) = s.split_once(':').ok_or_else(|| invalid("Missing card id"))?;
+        let id = header
+            .trim_start_matches("Card")
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| invalid("Invalid card id"))?;
+        let (winning_numbers, numbers) = numbers.split_once('|').ok_or_else(|| invalid("Missing numbers"))?;
+
+        let parse_numbers = |numbers: &str| {
+            numbers
+                .split_whitespace()
+                .map(|number| number.parse::<u32>().map_err(|_| invalid("Invalid number")))
+                .collect::<io::Result<HashSet<_>>>()
+        };
+
+        Ok(Self {
+            id,
+            winning_numbers: parse_numbers(winning_numbers)?,
+            numbers: parse_numbers(numbers)?,
+        })
+    }
+}
+
+impl Scratchcard {
+    /// How many of our numbers are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning_numbers.intersection(&self.numbers).count()
+    }
+}
@@ -0,0 +1,45 @@
+use num::Integer;
+
+/// Solves `x = a (mod m)` and `x = b (mod n)` together with the generalized Chinese remainder
+/// theorem, the moduli not needing to be coprime. Returns `(x, lcm(m, n))` with `0 <= x < lcm`,
+/// or `None` when the two congruences have no common solution.
+pub fn combine_congruences((a, m): (i128, i128), (b, n): (i128, i128)) -> Option<(i128, i128)> {
+    let egcd = m.extended_gcd(&n);
+    if (b - a) % egcd.gcd != 0 {
+        return None;
+    }
+
+    // `m * x = gcd (mod n)`, so `a + m * k` with `k = (b - a) / gcd * x` is `b (mod n)`
+    let lcm = m / egcd.gcd * n;
+    let k = ((b - a) / egcd.gcd).rem_euclid(n / egcd.gcd) * egcd.x.rem_euclid(n / egcd.gcd) % (n / egcd.gcd);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coprime_moduli() {
+        assert_eq!(combine_congruences((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(combine_congruences((0, 7), (0, 11)), Some((0, 77)));
+    }
+
+    #[test]
+    fn non_coprime_moduli() {
+        assert_eq!(combine_congruences((2, 4), (4, 6)), Some((10, 12)));
+        assert_eq!(combine_congruences((3, 6), (3, 6)), Some((3, 6)));
+    }
+
+    #[test]
+    fn unsolvable_congruences() {
+        assert_eq!(combine_congruences((1, 4), (2, 6)), None);
+        assert_eq!(combine_congruences((0, 2), (1, 2)), None);
+    }
+
+    #[test]
+    fn negative_residues() {
+        assert_eq!(combine_congruences((-1, 3), (-2, 5)), Some((8, 15)));
+        assert_eq!(combine_congruences((-2, 4), (-2, 6)), Some((10, 12)));
+    }
+}
@@ -0,0 +1,25 @@
+use std::{env, io};
+
+pub const DEFAULT_AOC_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/YassineElbouchaibi/AoC2023";
+
+/// `$AOC_BASE_URL`, or the Advent of Code website when it is not set, without trailing slash.
+pub fn aoc_base_url() -> String {
+    let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_AOC_BASE_URL.to_string());
+    base_url.trim_end_matches('/').to_string()
+}
+
+/// Fetches a page from `aoc_base_url()`, authenticated with `$AOC_SESSION` when it is set.
+pub fn fetch_aoc_page(path: &str) -> io::Result<String> {
+    let url = format!("{}/{}", aoc_base_url(), path.trim_start_matches('/'));
+
+    let mut request = ureq::get(&url).set("User-Agent", USER_AGENT);
+    if let Ok(session) = env::var("AOC_SESSION") {
+        request = request.set("Cookie", &format!("session={}", session));
+    }
+
+    request
+        .call()
+        .map_err(|err| io::Error::other(format!("Error fetching page: {}", err)))?
+        .into_string()
+}
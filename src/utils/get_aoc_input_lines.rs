@@ -1,4 +1,8 @@
-use std::{io::{self, BufRead}, fs::File, env};
+use std::{io::{self, BufRead}, env, path::Path};
+
+use crate::inputs::read_input_file;
+
+pub type AocInputLines = io::Lines<Box<dyn BufRead>>;
 
 pub fn get_input_file_path_from_args() -> String {
     let args: Vec<String> = env::args().collect();
@@ -12,8 +16,7 @@ pub fn get_input_file_path_from_args() -> String {
     file_path.to_string()
 }
 
-pub fn get_aoc_input_lines() -> io::Result<io::Lines<io::BufReader<File>>> {
+pub fn get_aoc_input_lines() -> io::Result<AocInputLines> {
     let file_path = get_input_file_path_from_args();
-    let file = File::open(file_path)?;
-    Ok(io::BufReader::new(file).lines())
-}
\ No newline at end of file
+    Ok(read_input_file(Path::new(&file_path))?.lines())
+}
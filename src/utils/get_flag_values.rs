@@ -0,0 +1,71 @@
+use std::env;
+
+/// Whether a flag such as `--route` was passed after the input file.
+pub fn has_flag(flag: &str) -> bool {
+    env::args().skip(1).any(|arg| arg == flag)
+}
+
+/// Returns the `count` values following the first occurrence of `flag`, e.g. `["10"]` for
+/// `--at 10`. Panics if the flag is not followed by as many values.
+pub fn get_flag_values(flag: &str, count: usize) -> Option<Vec<String>> {
+    get_all_flag_values(flag, count).into_iter().next()
+}
+
+/// Returns the values following every occurrence of a repeatable flag, in order.
+pub fn get_all_flag_values(flag: &str, count: usize) -> Vec<Vec<String>> {
+    find_flag_values(&env::args().skip(1).collect::<Vec<_>>(), flag, count)
+}
+
+fn find_flag_values(args: &[String], flag: &str, count: usize) -> Vec<Vec<String>> {
+    args.iter()
+        .enumerate()
+        .filter(|(_, arg)| *arg == flag)
+        .map(|(i, _)| {
+            let values = args.iter().skip(i + 1).take(count).cloned().collect::<Vec<_>>();
+            if values.len() < count || values.iter().any(|value| value.starts_with("--")) {
+                panic!("Error parsing {}, expected {} value(s)", flag, count);
+            }
+            values
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn values_follow_their_flag() {
+        let args = args("input.txt --test-area 7 27 --cut");
+        assert_eq!(find_flag_values(&args, "--test-area", 2), vec![vec!["7", "27"]]);
+        assert_eq!(find_flag_values(&args, "--cut", 0), vec![Vec::<String>::new()]);
+        assert!(find_flag_values(&args, "--route", 1).is_empty());
+    }
+
+    #[test]
+    fn repeated_flags_keep_their_order() {
+        let args = args("input.txt --vocabulary uno=1 --explain --vocabulary dos=2");
+        assert_eq!(find_flag_values(&args, "--vocabulary", 1), vec![vec!["uno=1"], vec!["dos=2"]]);
+    }
+
+    #[test]
+    fn negative_numbers_are_values() {
+        assert_eq!(find_flag_values(&args("input.txt --at -3"), "--at", 1), vec![vec!["-3"]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error parsing --map, expected 3 value(s)")]
+    fn missing_values_are_an_error() {
+        find_flag_values(&args("input.txt --map seed 79"), "--map", 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error parsing --at, expected 1 value(s)")]
+    fn flags_are_not_values() {
+        find_flag_values(&args("input.txt --at --degrees"), "--at", 1);
+    }
+}
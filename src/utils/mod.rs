@@ -1,2 +1,14 @@
+mod combine_congruences;
+mod fetch_aoc_page;
 mod get_aoc_input_lines;
-pub use get_aoc_input_lines::get_aoc_input_lines;
\ No newline at end of file
+mod get_flag_values;
+mod newton_polynomial;
+mod parse_day;
+mod range_map;
+pub use combine_congruences::combine_congruences;
+pub use fetch_aoc_page::{aoc_base_url, fetch_aoc_page, DEFAULT_AOC_BASE_URL};
+pub use get_aoc_input_lines::{get_aoc_input_lines, AocInputLines};
+pub use get_flag_values::{get_all_flag_values, get_flag_values, has_flag};
+pub use newton_polynomial::NewtonPolynomial;
+pub use parse_day::parse_day;
+pub use range_map::RangeMap;
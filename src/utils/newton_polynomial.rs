@@ -0,0 +1,102 @@
+use num::{BigInt, One, Zero};
+
+/// The polynomial through the samples `y(0), y(1), ..., y(n - 1)` of a sequence, in Newton
+/// forward form `y(x) = sum of D^k y(0) * C(x, k)`, where `D^k y(0)` is the first value of the
+/// k-th difference row. Integer samples give integer differences, and `C(x, k)` is an integer
+/// for any integer `x`, so the whole polynomial stays exact without rationals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewtonPolynomial {
+    /// First value of each difference row, up to the last one which is not all zeros.
+    leading_differences: Vec<BigInt>,
+    reaches_zero_row: bool,
+}
+
+impl NewtonPolynomial {
+    pub fn from_samples(samples: &[i64]) -> Self {
+        let mut row: Vec<BigInt> = samples.iter().map(|&sample| BigInt::from(sample)).collect();
+        let mut leading_differences = vec![];
+
+        while !row.iter().all(Zero::is_zero) {
+            leading_differences.push(row[0].clone());
+            row = row.windows(2).map(|pair| &pair[1] - &pair[0]).collect();
+        }
+
+        // Running out of values means the last row was never checked to be followed by zeros
+        let reaches_zero_row = !row.is_empty() || samples.is_empty();
+        Self {
+            leading_differences,
+            reaches_zero_row,
+        }
+    }
+
+    /// Degree of the polynomial, 0 for a constant or empty sequence.
+    pub fn degree(&self) -> usize {
+        self.leading_differences.len().saturating_sub(1)
+    }
+
+    /// Whether the differences of the samples end with a row of zeros. When they do not, the
+    /// polynomial still goes through every sample, but nothing supports extrapolating it.
+    pub fn reaches_zero_row(&self) -> bool {
+        self.reaches_zero_row
+    }
+
+    /// Value at `x`, which can be before the first sample or after the last one, in O(degree)
+    /// big integer operations.
+    pub fn value_at(&self, x: i64) -> BigInt {
+        let x = BigInt::from(x);
+        let mut binomial = BigInt::one();
+        let mut value = BigInt::zero();
+
+        for (k, difference) in self.leading_differences.iter().enumerate() {
+            value += difference * &binomial;
+            // `C(x, k + 1) = C(x, k) * (x - k) / (k + 1)`, the division being exact
+            binomial = binomial * (&x - k) / (k + 1);
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_at_extrapolates_both_ways() {
+        // y = x^2 - 3x + 1
+        let quadratic = NewtonPolynomial::from_samples(&[1, -1, -1, 1]);
+        assert_eq!(quadratic.value_at(-1), BigInt::from(5));
+        assert_eq!(quadratic.value_at(-10), BigInt::from(131));
+        assert_eq!(quadratic.value_at(1_000_000), BigInt::from(999_997_000_001_i64));
+    }
+
+    #[test]
+    fn value_at_does_not_overflow_far_away() {
+        // y = x^3, whose value at 10^12 does not fit in i64
+        let cubic = NewtonPolynomial::from_samples(&[0, 1, 8, 27, 64]);
+        assert_eq!(cubic.value_at(1_000_000_000_000), BigInt::from(10).pow(36));
+        assert_eq!(cubic.value_at(-3), BigInt::from(-27));
+    }
+
+    #[test]
+    fn degree_is_the_last_non_zero_row() {
+        assert_eq!(NewtonPolynomial::from_samples(&[0, 1, 8, 27, 64]).degree(), 3);
+        assert_eq!(NewtonPolynomial::from_samples(&[0, 3, 6, 9, 12, 15]).degree(), 1);
+    }
+
+    #[test]
+    fn short_histories() {
+        let empty = NewtonPolynomial::from_samples(&[]);
+        assert_eq!((empty.degree(), empty.reaches_zero_row()), (0, true));
+        assert_eq!(empty.value_at(3), BigInt::zero());
+
+        // A single value is not known to be constant until a second one confirms it
+        let single = NewtonPolynomial::from_samples(&[5]);
+        assert_eq!((single.degree(), single.reaches_zero_row()), (0, false));
+        assert_eq!(single.value_at(-1), BigInt::from(5));
+
+        let constant = NewtonPolynomial::from_samples(&[5, 5]);
+        assert_eq!((constant.degree(), constant.reaches_zero_row()), (0, true));
+        assert_eq!(constant.value_at(100), BigInt::from(5));
+    }
+}
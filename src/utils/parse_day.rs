@@ -0,0 +1,7 @@
+/// Parses a day argument such as `1` or `01` into its zero padded form (`01`).
+pub fn parse_day(day: &str) -> Option<String> {
+    match day.parse::<u32>() {
+        Ok(day @ 1..=25) => Some(format!("{:02}", day)),
+        _ => None,
+    }
+}
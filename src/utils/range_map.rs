@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+/// Piecewise shift of the integers: sorted disjoint intervals, each with an offset added to
+/// the values it holds. Values outside of every explicit interval map to themselves, and values
+/// shifted past either end of `i64` saturate there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    /// `(start, offset)` of each interval, which ends where the next one starts. The first one
+    /// starts at `i64::MIN` and the last one ends at `i64::MAX`, so that every value is covered.
+    segments: Vec<(i64, i64)>,
+}
+
+impl Default for RangeMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl RangeMap {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![(i64::MIN, 0)],
+        }
+    }
+
+    /// Builds a map from `(destination, source, length)` ranges, as written in the almanac.
+    /// Where source ranges overlap, the first one wins.
+    pub fn from_ranges(ranges: &[(i64, i64, i64)]) -> Self {
+        let mut map = Self::identity();
+        for &(destination, source, length) in ranges.iter().rev() {
+            map.insert(source..source + length, destination - source);
+        }
+
+        map
+    }
+
+    /// Shifts every value of `range` by `offset`, replacing what was there before.
+    pub fn insert(&mut self, range: Range<i64>, offset: i64) {
+        if range.is_empty() {
+            return;
+        }
+
+        let offset_after = self.segments[self.segment_index(range.end)].1;
+        self.segments.retain(|&(start, _)| start < range.start || start >= range.end);
+
+        let i = self.segments.partition_point(|&(start, _)| start < range.start);
+        self.segments.insert(i, (range.start, offset));
+        if range.end < i64::MAX && self.segments.get(i + 1).is_none_or(|&(start, _)| start != range.end) {
+            self.segments.insert(i + 1, (range.end, offset_after));
+        }
+
+        self.merge_segments();
+    }
+
+    pub fn get(&self, value: i64) -> i64 {
+        value.saturating_add(self.segments[self.segment_index(value)].1)
+    }
+
+    /// Every value mapped to `value`, of which there can be none or several.
+    pub fn preimage(&self, value: i64) -> Vec<i64> {
+        self.intervals()
+            .filter_map(|(interval, offset)| {
+                let candidate = value.checked_sub(offset)?;
+                interval.contains(&candidate).then_some(candidate)
+            })
+            .collect()
+    }
+
+    /// Intervals covering every value, with their offset.
+    pub fn intervals(&self) -> impl Iterator<Item = (Range<i64>, i64)> + '_ {
+        self.segments.iter().enumerate().map(|(i, &(start, offset))| {
+            let end = self.segments.get(i + 1).map_or(i64::MAX, |&(end, _)| end);
+            (start..end, offset)
+        })
+    }
+
+    /// Images of a set of intervals, as sorted disjoint intervals.
+    pub fn map_intervals(&self, intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        let mut images = intervals
+            .iter()
+            .filter(|interval| !interval.is_empty())
+            .flat_map(|interval| self.split(interval.clone()))
+            .map(|(piece, offset)| piece.start.saturating_add(offset)..piece.end.saturating_add(offset))
+            .collect::<Vec<_>>();
+        images.sort_by_key(|image| image.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(images.len());
+        for image in images {
+            match merged.last_mut() {
+                Some(last) if image.start <= last.end => last.end = last.end.max(image.end),
+                _ => merged.push(image),
+            }
+        }
+
+        merged
+    }
+
+    /// The map applying `self`, then `next`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        // Shifting keeps the order of the values, so the pieces come out sorted
+        let mut segments = vec![];
+        for (interval, offset) in self.intervals() {
+            // An image clamped at `i64::MIN` must still start at `interval.start` once shifted
+            // back, and one clamped down to nothing still needs a segment
+            let image = interval.start.saturating_add(offset)..interval.end.saturating_add(offset);
+            let mut pieces = next.split(image.clone()).collect::<Vec<_>>();
+            if pieces.is_empty() {
+                pieces.push((image.clone(), next.segments[next.segment_index(image.start)].1));
+            }
+
+            for (i, (piece, next_offset)) in pieces.into_iter().enumerate() {
+                let start = if i == 0 { interval.start } else { piece.start - offset };
+                segments.push((start, offset.saturating_add(next_offset)));
+            }
+        }
+
+        let mut map = RangeMap { segments };
+        map.merge_segments();
+        map
+    }
+
+    fn segment_index(&self, value: i64) -> usize {
+        self.segments.partition_point(|&(start, _)| start <= value) - 1
+    }
+
+    /// Cuts `range` at the interval boundaries, returning each piece with its offset.
+    fn split(&self, range: Range<i64>) -> impl Iterator<Item = (Range<i64>, i64)> + '_ {
+        self.intervals()
+            .skip(self.segment_index(range.start))
+            .take_while(move |(interval, _)| interval.start < range.end)
+            .map(move |(interval, offset)| (interval.start.max(range.start)..interval.end.min(range.end), offset))
+    }
+
+    fn merge_segments(&mut self) {
+        self.segments.dedup_by(|next, previous| next.1 == previous.1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(map: &RangeMap) -> Vec<(Range<i64>, i64)> {
+        map.intervals().collect()
+    }
+
+    #[test]
+    fn insert_splits_and_merges_intervals() {
+        let mut map = RangeMap::identity();
+        map.insert(10..20, 5);
+        map.insert(15..30, -2);
+        assert_eq!(
+            intervals(&map),
+            vec![(i64::MIN..10, 0), (10..15, 5), (15..30, -2), (30..i64::MAX, 0)]
+        );
+
+        map.insert(10..30, 0);
+        assert_eq!(map, RangeMap::identity());
+    }
+
+    #[test]
+    fn from_ranges_keeps_the_first_overlapping_range() {
+        let map = RangeMap::from_ranges(&[(50, 98, 2), (52, 50, 48), (0, 90, 20)]);
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(100), 10);
+        assert_eq!(map.get(110), 110);
+    }
+
+    #[test]
+    fn then_composes_the_maps() {
+        let first = RangeMap::from_ranges(&[(50, 98, 2), (52, 50, 48)]);
+        let second = RangeMap::from_ranges(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let composed = first.then(&second);
+
+        for value in -10..120 {
+            assert_eq!(composed.get(value), second.get(first.get(value)), "value {}", value);
+        }
+    }
+
+    #[test]
+    fn then_keeps_the_first_segment_at_i64_min() {
+        let mut map = RangeMap::identity();
+        map.insert(i64::MIN..0, -5);
+        let composed = map.then(&RangeMap::identity());
+
+        assert_eq!(composed, map);
+        assert_eq!(composed.get(i64::MIN), i64::MIN);
+        assert_eq!(composed.get(-1), -6);
+    }
+
+    #[test]
+    fn then_keeps_segments_shifted_entirely_out_of_range() {
+        let mut map = RangeMap::identity();
+        map.insert(i64::MIN..i64::MIN + 3, -5);
+        let mut next = RangeMap::identity();
+        next.insert(i64::MIN..-100, 7);
+        let composed = map.then(&next);
+
+        assert_eq!(intervals(&composed)[0], (i64::MIN..i64::MIN + 3, 2));
+        assert_eq!(composed.get(i64::MIN + 4), i64::MIN + 11);
+    }
+
+    #[test]
+    fn map_intervals_merges_overlapping_images() {
+        let map = RangeMap::from_ranges(&[(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(map.map_intervals(&[79..93, 55..68]), vec![57..70, 81..95]);
+        assert_eq!(map.map_intervals(&[96..100, 40..45]), vec![40..45, 50..52, 98..100]);
+        assert!(map.map_intervals(&[5..5, 8..8]).is_empty());
+    }
+
+    #[test]
+    fn preimage_finds_every_value_mapped_to_it() {
+        let map = RangeMap::from_ranges(&[(10, 0, 5)]);
+        assert_eq!(map.preimage(12), vec![2, 12]);
+        assert!(map.preimage(2).is_empty());
+        assert_eq!(map.preimage(7), vec![7]);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    env,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+use advent_of_code_2023::{
+    puzzles::{extract_examples, puzzle_to_markdown},
+    utils::{aoc_base_url, fetch_aoc_page},
+};
+
+const DAY_PAGE: &str = include_str!("fixtures/day-page.html");
+
+const DAY_MARKDOWN: &str = "## Day 1: Trebuchet?!
+
+Something is wrong with global snow production, and you've been selected to take a look. See the [about page](http://aoc.test/2023/about) or [this](https://example.com/snow).
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these lines are `12` and `38`.
+
+- Combine the **first** digit
+- and the **last** digit.
+
+Adding these together produces **50**:
+
+```
+12 + 38 = 50
+```
+
+## Part Two
+
+Here is another example:
+
+```
+two1nine
+eightwothree
+```
+
+Then the first example again, for example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+";
+
+/// Serves `body` to a single request on a local port, returning the base URL to reach it.
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+            line.clear();
+        }
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        (&stream).write_all(response.as_bytes()).unwrap();
+    });
+
+    format!("http://{}/", address)
+}
+
+#[test]
+fn puzzle_to_markdown_converts_both_parts() {
+    assert_eq!(puzzle_to_markdown(DAY_PAGE, "http://aoc.test"), DAY_MARKDOWN);
+}
+
+#[test]
+fn puzzle_to_markdown_links_to_the_given_site() {
+    let markdown = puzzle_to_markdown(DAY_PAGE, "http://localhost:8080/");
+    assert!(markdown.contains("[about page](http://localhost:8080/2023/about)"));
+}
+
+#[test]
+fn extract_examples_keeps_distinct_introduced_code_blocks() {
+    assert_eq!(extract_examples(DAY_PAGE), vec!["1abc2\npqr3stu8vwx\n", "two1nine\neightwothree\n"]);
+}
+
+#[test]
+fn day_page_is_read_from_the_configured_site() {
+    let base_url = serve_once(DAY_PAGE);
+    env::set_var("AOC_BASE_URL", &base_url);
+
+    let html = fetch_aoc_page("2023/day/1").unwrap();
+    let markdown = puzzle_to_markdown(&html, &aoc_base_url());
+
+    assert!(markdown.starts_with("## Day 1: Trebuchet?!\n"));
+    assert!(markdown.contains(&format!("[about page]({}2023/about)", base_url)));
+    assert_eq!(extract_examples(&html).len(), 2);
+}